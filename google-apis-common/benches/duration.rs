@@ -0,0 +1,35 @@
+//! Compares parsing durations of whole seconds, which take a fast path, with parsing durations
+//! which have a fraction.
+//!
+//! Run with `cargo bench -p google-apis-common --bench duration`.
+use std::hint::black_box;
+use std::time::Instant;
+
+use google_apis_common::serde::duration;
+use serde::de::value::{BorrowedStrDeserializer, Error};
+use serde_with::DeserializeAs;
+
+const ITERATIONS: u32 = 1_000_000;
+
+fn parse(s: &str) -> chrono::Duration {
+    duration::Wrapper::deserialize_as(BorrowedStrDeserializer::<Error>::new(s)).unwrap()
+}
+
+fn main() {
+    for input in ["3600s", "3600.0s", "3600.5s", "-0.000000001s"] {
+        // warm up
+        for _ in 0..ITERATIONS / 10 {
+            black_box(parse(black_box(input)));
+        }
+        let start = Instant::now();
+        for _ in 0..ITERATIONS {
+            black_box(parse(black_box(input)));
+        }
+        let elapsed = start.elapsed();
+        println!(
+            "{:>16}: {:>6.1} ns/iter",
+            input,
+            elapsed.as_nanos() as f64 / f64::from(ITERATIONS)
+        );
+    }
+}
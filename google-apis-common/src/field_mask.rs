@@ -1,12 +1,21 @@
+use std::borrow::Cow;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Map, Value};
 
 fn titlecase(source: &str, dest: &mut String) {
     let mut underscore = false;
+    let mut in_quotes = false;
     for c in source.chars() {
-        if c == '_' {
+        if c == '`' {
+            in_quotes = !in_quotes;
+            dest.push(c);
+        } else if in_quotes {
+            // quoted segments, like map keys, are literals
+            dest.push(c);
+        } else if c == '_' {
             underscore = true;
         } else if underscore {
             dest.push(c.to_ascii_uppercase());
@@ -19,6 +28,32 @@ fn titlecase(source: &str, dest: &mut String) {
 
 fn snakecase(source: &str) -> String {
     let mut dest = String::with_capacity(source.len() + 5);
+    snakecase_into(source, &mut dest);
+    dest
+}
+
+/// Writes the snake_case form of the camelCase `source` into `dest`, which is cleared first, so
+/// that one buffer can be reused when converting many paths.
+///
+/// Backtick-quoted segments are copied verbatim.
+pub fn snakecase_into(source: &str, dest: &mut String) {
+    dest.clear();
+    // every other part is within backticks
+    for (i, part) in source.split('`').enumerate() {
+        if i > 0 {
+            dest.push('`');
+        }
+        if i % 2 == 0 {
+            snakecase_unquoted_into(part, dest);
+        } else {
+            dest.push_str(part);
+        }
+    }
+}
+
+/// Appends the snake_case form of the camelCase `source` to `dest`, treating backticks like any
+/// other character.
+fn snakecase_unquoted_into(source: &str, dest: &mut String) {
     for c in source.chars() {
         if c.is_ascii_uppercase() {
             dest.push('_');
@@ -27,13 +62,895 @@ fn snakecase(source: &str) -> String {
             dest.push(c);
         }
     }
+}
+
+/// Returns the length of the camelCase form of the unquoted `segment`, as written by
+/// [`titlecase`] and [`escape_path_segment`], without building it.
+fn titlecase_len(segment: &str) -> usize {
+    let mut len = 0;
+    let mut backticks = 0;
+    let mut needs_quotes = false;
+    for c in segment.chars() {
+        // titlecase drops underscores, and keeps the length of all other characters
+        if c == '_' && backticks % 2 == 0 {
+            continue;
+        }
+        if c == '`' {
+            backticks += 1;
+        }
+        needs_quotes |= matches!(c, '.' | ',' | '`') || c.is_whitespace();
+        len += c.len_utf8();
+    }
+    if needs_quotes {
+        len + 2 + backticks
+    } else {
+        len
+    }
+}
+
+/// Renames the keys of all objects within `value` from camelCase to snake_case, like the fields
+/// of a [`FieldMask`], e.g. to deserialize the response of a camelCase API into snake_case types.
+///
+/// Every key is renamed, including those of objects which hold user data rather than fields, like
+/// the keys of a `labels` map, so such values should be moved out of `value` first. Keys are
+/// converted as a whole, without the special meaning of backticks in paths.
+///
+/// Keys which are snake_case already are kept. If two keys of an object convert to the same name,
+/// like `fooBar` and `foo_bar`, an error is returned rather than dropping either value, and
+/// `value` is left partially converted.
+pub fn json_keys_to_snake_case(value: &mut Value) -> Result<(), KeyCollisionError> {
+    match value {
+        Value::Object(object) => {
+            let mut renamed = Map::new();
+            // the original of every renamed key, to report collisions
+            let mut originals = std::collections::HashMap::new();
+            for (camel_case, mut child) in std::mem::take(object) {
+                json_keys_to_snake_case(&mut child)?;
+                let mut key = String::with_capacity(camel_case.len() + 5);
+                snakecase_unquoted_into(&camel_case, &mut key);
+                if let Some(first) = originals.remove(&key) {
+                    return Err(KeyCollisionError {
+                        first,
+                        second: camel_case,
+                        key,
+                    });
+                }
+                originals.insert(key.clone(), camel_case);
+                renamed.insert(key, child);
+            }
+            *object = renamed;
+        }
+        Value::Array(array) => {
+            for item in array {
+                json_keys_to_snake_case(item)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// The error returned by [`json_keys_to_snake_case`] if two keys of an object convert to the same
+/// snake_case name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyCollisionError {
+    pub first: String,
+    pub second: String,
+    /// The snake_case name of both keys.
+    pub key: String,
+}
+
+impl Display for KeyCollisionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "keys '{}' and '{}' both convert to '{}'",
+            self.first, self.second, self.key
+        )
+    }
+}
+
+impl std::error::Error for KeyCollisionError {}
+
+/// Returns `path` with underscores removed and letters lowercased outside of backtick-quoted
+/// segments, so that the camelCase and snake_case forms of a path, in any casing, are equal.
+fn fold_case(path: &str) -> String {
+    let mut dest = String::with_capacity(path.len());
+    let mut in_quotes = false;
+    for c in path.chars() {
+        if c == '`' {
+            in_quotes = !in_quotes;
+            dest.push(c);
+        } else if in_quotes {
+            dest.push(c);
+        } else if c != '_' {
+            dest.push(c.to_ascii_lowercase());
+        }
+    }
     dest
 }
 
+/// The path of a mask covering all fields.
+const ALL: &str = "*";
+
+/// Quotes `segment` in backticks if it contains a `.`, `,`, backtick or whitespace, which can't
+/// appear in a path otherwise. Backticks within it are doubled.
+pub fn escape_path_segment(segment: &str) -> Cow<'_, str> {
+    if segment.contains(|c: char| matches!(c, '.' | ',' | '`') || c.is_whitespace()) {
+        Cow::Owned(format!("`{}`", segment.replace('`', "``")))
+    } else {
+        Cow::Borrowed(segment)
+    }
+}
+
+/// Reverses [`escape_path_segment`] for the part of a quoted segment after its opening backtick,
+/// removing the closing backtick and un-doubling the ones within.
+fn unescape_quoted(quoted: &str) -> String {
+    quoted
+        .strip_suffix('`')
+        .unwrap_or(quoted)
+        .replace("``", "`")
+}
+
+/// Returns true if `path` is a proper descendant of `ancestor`, e.g. `user.name` of `user`.
+///
+/// The `.` following `ancestor` must separate segments, rather than be part of a quoted key, so
+/// ``labels.`a.b` `` isn't a descendant of ``labels.`a``.
+fn is_descendant_of(path: &str, ancestor: &str) -> bool {
+    descendant_rest(path, ancestor).is_some()
+}
+
+/// Returns the rest of `path` after `ancestor` and the separating `.`, if `path` is a proper
+/// descendant of `ancestor`.
+// `usize::is_multiple_of` needs Rust 1.87
+#[allow(clippy::manual_is_multiple_of)]
+fn descendant_rest<'a>(path: &'a str, ancestor: &str) -> Option<&'a str> {
+    let rest = path.strip_prefix(ancestor)?.strip_prefix('.')?;
+    // an odd number of backticks leaves the `.` within a quoted segment
+    (ancestor.matches('`').count() % 2 == 0).then_some(rest)
+}
+
+/// Splits `path` into its segments at every `.` outside of backtick-quoted segments, which are
+/// returned including their backticks.
+fn split_segments(path: &str) -> Vec<&str> {
+    let mut in_quotes = false;
+    let mut prev_ind = 0;
+    let mut segments = Vec::new();
+    for (i, c) in path.char_indices() {
+        if c == '`' {
+            in_quotes = !in_quotes;
+        } else if !in_quotes && c == '.' {
+            segments.push(&path[prev_ind..i]);
+            prev_ind = i + 1;
+        }
+    }
+    segments.push(&path[prev_ind..]);
+    segments
+}
+
+/// Returns true if `segment` is an index into a repeated field, like the `0` of `items.0.name`.
+fn is_index(segment: &str) -> bool {
+    !segment.is_empty() && segment.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Checks that `path` is a valid snake_case path, or `*`.
+fn validate_path(path: &str) -> Result<(), FieldMaskError> {
+    if path.is_empty() {
+        return Err(FieldMaskError::EmptyPath);
+    }
+    if path == ALL {
+        return Ok(());
+    }
+    if path.chars().filter(|c| *c == '`').count() % 2 != 0 {
+        return Err(FieldMaskError::UnterminatedQuote {
+            path: path.to_string(),
+        });
+    }
+    for (i, segment) in split_segments(path).into_iter().enumerate() {
+        let is_quoted = segment.len() >= 2 && segment.starts_with('`') && segment.ends_with('`');
+        let is_field_name = segment.starts_with(|c: char| c.is_ascii_lowercase())
+            && segment
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+        // an index into a repeated field can't be the first segment, as messages aren't arrays
+        let is_index = i > 0 && is_index(segment);
+        if !(is_quoted || is_field_name || is_index) {
+            return Err(FieldMaskError::InvalidSegment {
+                path: path.to_string(),
+                segment: segment.to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// The error returned when a path can't be part of a [`FieldMask`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldMaskError {
+    /// The path was empty.
+    EmptyPath,
+    /// A backtick-quoted segment was not closed.
+    UnterminatedQuote { path: String },
+    /// A segment was neither a field name nor a backtick-quoted key.
+    InvalidSegment { path: String, segment: String },
+    /// A segment didn't name a known field of its message.
+    UnknownField { path: String, segment: String },
+    /// A JSON value was neither a string nor an array of strings, e.g. `number`.
+    UnexpectedJson { found: &'static str },
+}
+
+impl Display for FieldMaskError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FieldMaskError::EmptyPath => f.write_str("field mask paths must not be empty"),
+            FieldMaskError::UnterminatedQuote { path } => {
+                write!(f, "unterminated backtick in field mask path '{}'", path)
+            }
+            FieldMaskError::InvalidSegment { path, segment } => write!(
+                f,
+                "invalid segment '{}' in field mask path '{}'",
+                segment, path
+            ),
+            FieldMaskError::UnknownField { path, segment } => write!(
+                f,
+                "unknown field '{}' in field mask path '{}'",
+                segment, path
+            ),
+            FieldMaskError::UnexpectedJson { found } => write!(
+                f,
+                "expected a string or an array of strings as field mask, got {}",
+                found
+            ),
+        }
+    }
+}
+
+impl std::error::Error for FieldMaskError {}
+
+/// Maps between the names and numbers of fields, as the binary protobuf encoding of a
+/// `FieldMask` uses field numbers instead of names.
+///
+/// A message is identified by the field numbers leading to it from the message the mask applies
+/// to, so the top-level message is `&[]`, and the message in its field 3 is `&[3]`.
+#[cfg(feature = "numeric-paths")]
+pub trait FieldNumberLookup {
+    /// Returns the number of the field called `field`, in snake_case, of the message at `parent`.
+    fn field_number(&self, parent: &[u32], field: &str) -> Option<u32>;
+
+    /// Returns the snake_case name of the field numbered `number` of the message at `parent`.
+    fn field_name(&self, parent: &[u32], number: u32) -> Option<String>;
+}
+
+/// A segment of a path, as it addresses a JSON value.
+#[derive(Debug, PartialEq)]
+enum Key {
+    /// The key of an object, which is camelCase, or verbatim without the backticks for quoted
+    /// segments.
+    Field(String),
+    /// The index of an element of an array, which only matches arrays.
+    Index(usize),
+}
+
+fn json_keys(path: &str) -> Vec<Key> {
+    split_segments(path)
+        .into_iter()
+        .map(|segment| match segment.strip_prefix('`') {
+            Some(quoted) => Key::Field(unescape_quoted(quoted)),
+            None if is_index(segment) => match segment.parse() {
+                Ok(index) => Key::Index(index),
+                Err(_) => Key::Field(segment.to_string()),
+            },
+            None => {
+                let mut key = String::with_capacity(segment.len());
+                titlecase(segment, &mut key);
+                Key::Field(key)
+            }
+        })
+        .collect()
+}
+
+fn child<'a>(value: &'a Value, key: &Key) -> Option<&'a Value> {
+    match key {
+        Key::Field(field) => value.as_object()?.get(field),
+        Key::Index(index) => value.as_array()?.get(*index),
+    }
+}
+
+fn child_mut<'a>(value: &'a mut Value, key: &Key) -> Option<&'a mut Value> {
+    match key {
+        Key::Field(field) => value.as_object_mut()?.get_mut(field),
+        Key::Index(index) => value.as_array_mut()?.get_mut(*index),
+    }
+}
+
+fn lookup<'a>(value: &'a Value, keys: &[Key]) -> Option<&'a Value> {
+    keys.iter().try_fold(value, child)
+}
+
+/// Sets the value at `keys`, replacing anything in the way of a field that isn't an object.
+///
+/// Array elements can't be created, so nothing is set if an index is out of bounds or doesn't
+/// refer to an array.
+fn insert(target: &mut Value, keys: &[Key], value: Value) {
+    let mut target = target;
+    for (i, key) in keys.iter().enumerate() {
+        let slot = match key {
+            Key::Field(field) => {
+                if !target.is_object() {
+                    *target = Value::Object(Map::new());
+                }
+                target
+                    .as_object_mut()
+                    .unwrap()
+                    .entry(field.as_str())
+                    .or_insert(Value::Null)
+            }
+            Key::Index(index) => match target.as_array_mut().and_then(|a| a.get_mut(*index)) {
+                Some(element) => element,
+                None => return,
+            },
+        };
+        if i == keys.len() - 1 {
+            *slot = value;
+            return;
+        }
+        target = slot;
+    }
+}
+
+/// Removes the field at `keys`. Array elements are kept, as removing one would shift the indices
+/// of the elements following it.
+fn remove(target: &mut Value, keys: &[Key]) {
+    let (last, parents) = keys.split_last().expect("paths have at least one segment");
+    let parent = parents.iter().try_fold(target, child_mut);
+    if let (Some(Value::Object(parent)), Key::Field(field)) = (parent, last) {
+        parent.remove(field);
+    }
+}
+
+/// The order of the fields of objects projected by [`FieldMask::project_with_order`].
+///
+/// Only takes effect with the `preserve-order` feature, as JSON objects are otherwise sorted by
+/// key regardless.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ProjectionOrder {
+    /// Fields keep their order in the source object, which gives stable diffs.
+    #[default]
+    SourceOrder,
+    /// Fields are ordered by the first path of the mask selecting them.
+    MaskOrder,
+}
+
+/// Returns the parts of `value` selected by `paths`, or `None` if nothing is selected. Arrays
+/// only keep their selected elements, in order.
+fn project(value: &Value, paths: &[&[Key]], order: ProjectionOrder) -> Option<Value> {
+    if paths.iter().any(|keys| keys.is_empty()) {
+        return Some(value.clone());
+    }
+    let selected = |key: &Key| -> Vec<&[Key]> {
+        paths
+            .iter()
+            .filter(|keys| &keys[0] == key)
+            .map(|keys| &keys[1..])
+            .collect()
+    };
+    match value {
+        Value::Object(object) => {
+            let fields: Vec<_> = match order {
+                ProjectionOrder::SourceOrder => object.iter().collect(),
+                ProjectionOrder::MaskOrder => {
+                    let mut fields = Vec::new();
+                    for keys in paths {
+                        if let Key::Field(field) = &keys[0] {
+                            if let Some(entry) = object.get_key_value(field) {
+                                if !fields.contains(&entry) {
+                                    fields.push(entry);
+                                }
+                            }
+                        }
+                    }
+                    fields
+                }
+            };
+            let projected: Map<_, _> = fields
+                .into_iter()
+                .filter_map(|(field, child)| {
+                    let paths = selected(&Key::Field(field.clone()));
+                    Some((field.clone(), project(child, &paths, order)?))
+                })
+                .collect();
+            (!projected.is_empty()).then_some(Value::Object(projected))
+        }
+        Value::Array(array) => {
+            let projected: Vec<_> = array
+                .iter()
+                .enumerate()
+                .filter_map(|(index, element)| {
+                    project(element, &selected(&Key::Index(index)), order)
+                })
+                .collect();
+            (!projected.is_empty()).then_some(Value::Array(projected))
+        }
+        _ => None,
+    }
+}
+
+/// Types which know the names of their fields, so that a mask requesting all of them can be
+/// built with [`FieldMask::from_struct_fields`].
+///
+/// It can be derived for structs with `#[derive(FieldNames)]` of `google-apis-derive`.
+pub trait FieldNames {
+    /// Returns the snake_case names of all fields.
+    fn field_names() -> &'static [&'static str];
+}
+
 /// A `FieldMask` as defined in `https://github.com/protocolbuffers/protobuf/blob/ec1a70913e5793a7d0a7b5fbf7e0e4f75409dd41/src/google/protobuf/field_mask.proto#L180`
-#[derive(Clone, Debug, Default, PartialEq, Eq)]
+///
+/// Paths are stored in snake_case, and converted from and to camelCase for JSON. Segments quoted
+/// in backticks, like map keys, are kept verbatim, including the backticks.
+#[derive(Clone, Default, PartialEq, Eq)]
 pub struct FieldMask(Vec<String>);
 
+/// Prints the stored snake_case paths, e.g. `FieldMask { paths: ["user.name", "photo"] }`.
+impl std::fmt::Debug for FieldMask {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FieldMask").field("paths", &self.0).finish()
+    }
+}
+
+impl FieldMask {
+    /// Creates a mask from paths in either camelCase or snake_case, keeping their order.
+    pub(crate) fn from_unnormalized_paths<I, P>(paths: I) -> FieldMask
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<str>,
+    {
+        FieldMask(
+            paths
+                .into_iter()
+                .map(|path| snakecase(path.as_ref()))
+                .collect(),
+        )
+    }
+
+    /// Creates a normalized mask from paths in either camelCase or snake_case.
+    fn from_paths<I, P>(paths: I) -> FieldMask
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<str>,
+    {
+        let mut mask = FieldMask::from_unnormalized_paths(paths);
+        mask.normalize();
+        mask
+    }
+
+    /// Creates a mask from snake_case paths, stored verbatim and in order.
+    ///
+    /// No case conversion, normalization or validation is done, which avoids lossy round trips
+    /// through camelCase for paths that are canonical already, e.g. from a normalized mask.
+    pub fn for_paths<I: IntoIterator<Item = String>>(paths: I) -> FieldMask {
+        FieldMask(paths.into_iter().collect())
+    }
+
+    /// Returns a builder for a mask which keeps its paths in the order they were first added,
+    /// e.g. for readable diffs, unlike masks which are [`normalize`](FieldMask::normalize)d.
+    pub fn ordered_builder() -> OrderedFieldMaskBuilder {
+        OrderedFieldMaskBuilder::default()
+    }
+
+    /// Returns a normalized mask of all fields declared by `T`.
+    pub fn from_struct_fields<T: FieldNames>() -> FieldMask {
+        FieldMask::from_paths(T::field_names())
+    }
+
+    /// Appends `path`, given in either camelCase or snake_case, unless it is present already.
+    ///
+    /// Fails if the path is invalid, in which case the mask is left unchanged.
+    pub fn push(&mut self, path: impl AsRef<str>) -> Result<(), FieldMaskError> {
+        let path = snakecase(path.as_ref());
+        validate_path(&path)?;
+        if !self.0.contains(&path) {
+            self.0.push(path);
+        }
+        Ok(())
+    }
+
+    /// Returns true if the mask has no paths.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns a mask covering all fields, consisting of the single path `*`.
+    ///
+    /// This follows the convention of update methods, where a mask of `*` requests a full
+    /// replacement. Note that an empty mask is *not* treated as covering all fields, even though
+    /// some methods interpret it that way as well.
+    pub fn all() -> FieldMask {
+        FieldMask(vec![ALL.to_string()])
+    }
+
+    /// Returns true if this mask covers all fields, see [`all`](FieldMask::all).
+    pub fn is_all(&self) -> bool {
+        self.0.iter().any(|path| path == ALL)
+    }
+
+    /// Returns true if `path` is covered by this mask, which is the case if the mask contains
+    /// `path` or one of its ancestors, or if it covers [`all`](FieldMask::all) fields.
+    ///
+    /// `path` uses the same snake_case form as the stored paths.
+    pub fn contains(&self, path: &str) -> bool {
+        self.0
+            .iter()
+            .any(|p| p == ALL || p == path || is_descendant_of(path, p))
+    }
+
+    /// Splits `candidates` into the paths which are covered by this mask and those which aren't,
+    /// as decided by [`contains`](FieldMask::contains), keeping their order.
+    pub fn partition_paths<'a>(&self, candidates: &'a [&str]) -> (Vec<&'a str>, Vec<&'a str>) {
+        candidates.iter().partition(|path| self.contains(path))
+    }
+
+    /// Like [`contains`](FieldMask::contains), but ignores the casing of `path` and of the stored
+    /// paths, as well as underscores, so that `displayName`, `display_name` and `DisplayName` all
+    /// match a stored `display_name`. Backtick-quoted segments are still compared exactly.
+    ///
+    /// This is meant for tolerating clients which send inconsistently cased masks.
+    pub fn contains_ignore_case(&self, path: &str) -> bool {
+        let path = fold_case(path);
+        self.0.iter().any(|p| {
+            let p = fold_case(p);
+            p == ALL || p == path || is_descendant_of(&path, &p)
+        })
+    }
+
+    /// Returns the first segment of every path, i.e. the top-level fields which are touched by the
+    /// mask, e.g. `user` and `photo` for `user.name,user.email,photo`.
+    ///
+    /// A mask covering [`all`](FieldMask::all) fields yields `*`.
+    pub fn top_level_fields(&self) -> std::collections::BTreeSet<String> {
+        self.0
+            .iter()
+            .map(|path| split_segments(path)[0].to_string())
+            .collect()
+    }
+
+    /// Returns the longest sequence of leading segments shared by all paths, e.g. `user` for
+    /// `user.name,user.email`, to route a partial update to the handler of a sub-message.
+    ///
+    /// Returns `None` if the paths share no segment, or the mask is empty or covers all fields.
+    /// A mask with a single path returns that path.
+    pub fn common_prefix(&self) -> Option<String> {
+        if self.is_all() {
+            return None;
+        }
+        let (first, rest) = self.0.split_first()?;
+        let mut prefix = split_segments(first);
+        for path in rest {
+            let segments = split_segments(path);
+            let shared = prefix
+                .iter()
+                .zip(&segments)
+                .take_while(|(a, b)| a == b)
+                .count();
+            prefix.truncate(shared);
+        }
+        if prefix.is_empty() {
+            None
+        } else {
+            Some(prefix.join("."))
+        }
+    }
+
+    /// Returns every path split into its segments, in the stored snake_case form. Dots within
+    /// backtick-quoted segments don't split them, and quoted segments keep their backticks.
+    pub fn segmented(&self) -> impl Iterator<Item = Vec<&str>> {
+        self.0.iter().map(|path| split_segments(path))
+    }
+
+    /// Returns the number of segments of the deepest path, or 0 for an empty mask.
+    ///
+    /// `user.address.city` has a depth of 3, and ``labels.`a.b` `` of 2, as quoted dots don't
+    /// count.
+    pub fn max_depth(&self) -> usize {
+        self.0
+            .iter()
+            .map(|path| split_segments(path).len())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns the number of segments of `path`, if it is one of the paths of this mask.
+    ///
+    /// `path` uses the same snake_case form as the stored paths.
+    pub fn depth_of(&self, path: &str) -> Option<usize> {
+        self.0
+            .iter()
+            .find(|p| *p == path)
+            .map(|path| split_segments(path).len())
+    }
+
+    /// Brings the mask into canonical form: paths are sorted, duplicates are removed, and
+    /// paths which are covered by one of their ancestors are dropped, e.g. `user.name` if `user`
+    /// is present as well. A mask covering all fields is reduced to `*`.
+    pub fn normalize(&mut self) {
+        if self.is_all() {
+            *self = FieldMask::all();
+            return;
+        }
+        self.0.sort_unstable();
+        self.0.dedup();
+        let paths = self.0.clone();
+        self.0.retain(|path| {
+            !paths
+                .iter()
+                .any(|ancestor| is_descendant_of(path, ancestor))
+        });
+    }
+
+    /// Checks that no path is covered by another path of the mask, such as `user.name` by `user`
+    /// or any path by `*`, which makes a mask ambiguous for updates.
+    ///
+    /// Fails with the redundant paths, in their stored snake_case form and order. Servers may
+    /// reject such masks, or [`normalize`](FieldMask::normalize) them instead.
+    pub fn check_no_redundancy(&self) -> Result<(), Vec<String>> {
+        let all = self.is_all();
+        let redundant: Vec<_> = self
+            .0
+            .iter()
+            .filter(|path| {
+                (all && *path != ALL)
+                    || self
+                        .0
+                        .iter()
+                        .any(|ancestor| is_descendant_of(path, ancestor))
+            })
+            .cloned()
+            .collect();
+        if redundant.is_empty() {
+            Ok(())
+        } else {
+            Err(redundant)
+        }
+    }
+
+    /// Returns true if both masks cover the same fields, i.e. are equal once
+    /// [`normalize`](FieldMask::normalize)d.
+    ///
+    /// In contrast, `==` compares the paths exactly as they are stored, including their order, so
+    /// `a,b` and `b,a` are `canonical_eq`, but not `==`.
+    pub fn canonical_eq(&self, other: &FieldMask) -> bool {
+        let mut this = self.clone();
+        this.normalize();
+        let mut other = other.clone();
+        other.normalize();
+        this == other
+    }
+
+    /// Returns a copy of the mask with its paths sorted and exact duplicates removed.
+    ///
+    /// Unlike [`normalize`](FieldMask::normalize), paths covered by an ancestor are kept, so
+    /// `user` and `user.name` both remain. This makes it suitable for deterministic display of a
+    /// mask exactly as it was specified.
+    pub fn sorted(&self) -> FieldMask {
+        let mut paths = self.0.clone();
+        paths.sort_unstable();
+        paths.dedup();
+        FieldMask(paths)
+    }
+
+    /// Returns the mask with `prefix.` prepended to every path, the inverse of
+    /// [`strip_prefix`](FieldMask::strip_prefix), to re-root the mask of a sub-message into a
+    /// mask of its parent.
+    ///
+    /// A mask covering [`all`](FieldMask::all) fields becomes `prefix` itself. `prefix` uses the
+    /// same snake_case form as the stored paths.
+    pub fn with_prefix(&self, prefix: &str) -> FieldMask {
+        if self.is_all() {
+            return FieldMask(vec![prefix.to_string()]);
+        }
+        FieldMask(
+            self.0
+                .iter()
+                .map(|path| format!("{}.{}", prefix, path))
+                .collect(),
+        )
+    }
+
+    /// Returns the comma-separated paths in camelCase, as used in JSON and by [`Display`].
+    pub fn to_camel_case_string(&self) -> String {
+        let mut repr = String::new();
+        let mut segment_repr = String::new();
+        for path in &self.0 {
+            for segment in split_segments(path) {
+                if segment.starts_with('`') {
+                    repr.push_str(segment);
+                } else {
+                    segment_repr.clear();
+                    titlecase(segment, &mut segment_repr);
+                    repr.push_str(&escape_path_segment(&segment_repr));
+                }
+                repr.push('.');
+            }
+            repr.pop();
+            repr.push(',');
+        }
+        repr.pop();
+        repr
+    }
+
+    /// Returns the length in bytes of [`to_camel_case_string`](FieldMask::to_camel_case_string),
+    /// without allocating it, e.g. to check a request against URL length limits up front.
+    pub fn serialized_len(&self) -> usize {
+        let paths: usize = self
+            .0
+            .iter()
+            .map(|path| {
+                let segments = split_segments(path);
+                let len: usize = segments
+                    .iter()
+                    .map(|segment| {
+                        if segment.starts_with('`') {
+                            segment.len()
+                        } else {
+                            titlecase_len(segment)
+                        }
+                    })
+                    .sum();
+                len + segments.len() - 1
+            })
+            .sum();
+        paths + self.0.len().saturating_sub(1)
+    }
+
+    /// Returns the comma-separated paths in snake_case, as they are stored and used by gRPC.
+    pub fn to_snake_case_string(&self) -> String {
+        self.0.join(",")
+    }
+
+    /// Returns the sub-mask rooted at `prefix`, with `prefix` stripped from every path.
+    ///
+    /// Paths which are not nested below `prefix` are dropped. If the mask
+    /// [`contains`](FieldMask::contains) `prefix`, e.g. because it contains `prefix` itself or an
+    /// ancestor of it, the whole sub-message is covered and [`all`](FieldMask::all) is returned.
+    /// `prefix` uses the same snake_case form as the stored paths, e.g. `user` or `user.address`.
+    pub fn strip_prefix(&self, prefix: &str) -> FieldMask {
+        if self.contains(prefix) {
+            return FieldMask::all();
+        }
+        FieldMask(
+            self.0
+                .iter()
+                .filter_map(|path| descendant_rest(path, prefix))
+                .map(str::to_string)
+                .collect(),
+        )
+    }
+
+    /// Returns a copy of `value`, a JSON object as produced by the proto3 JSON mapping, with only
+    /// the fields selected by this mask.
+    ///
+    /// An index segment like the `0` of `items.0.name` selects an element of an array, and the
+    /// result only contains the selected elements. Index segments never match objects.
+    ///
+    /// Fields keep their order in `value`, see [`project_with_order`](Self::project_with_order).
+    pub fn project(&self, value: &Value) -> Value {
+        self.project_with_order(value, ProjectionOrder::default())
+    }
+
+    /// Like [`project`](FieldMask::project), but with the fields of objects in the given `order`.
+    ///
+    /// Array elements always keep their order.
+    pub fn project_with_order(&self, value: &Value, order: ProjectionOrder) -> Value {
+        if self.is_all() {
+            return value.clone();
+        }
+        let paths: Vec<_> = self.0.iter().map(|path| json_keys(path)).collect();
+        let paths: Vec<_> = paths.iter().map(Vec::as_slice).collect();
+        project(value, &paths, order).unwrap_or_else(|| Value::Object(Map::new()))
+    }
+
+    /// Converts every path into the field numbers of its segments, e.g. `user.display_name` into
+    /// `[1, 2]` if `user` is field 1 of the message, and `display_name` field 2 of `user`.
+    ///
+    /// Fails for segments which aren't known to `descriptor`, which includes `*`, map keys and array
+    /// indices, as these can't be expressed by field numbers.
+    #[cfg(feature = "numeric-paths")]
+    pub fn to_numeric_paths(
+        &self,
+        descriptor: &impl FieldNumberLookup,
+    ) -> Result<Vec<Vec<u32>>, FieldMaskError> {
+        self.0
+            .iter()
+            .map(|path| {
+                let mut numbers = Vec::new();
+                for segment in split_segments(path) {
+                    let number = descriptor.field_number(&numbers, segment).ok_or_else(|| {
+                        FieldMaskError::UnknownField {
+                            path: path.clone(),
+                            segment: segment.to_string(),
+                        }
+                    })?;
+                    numbers.push(number);
+                }
+                Ok(numbers)
+            })
+            .collect()
+    }
+
+    /// Creates a mask from paths of field numbers, the inverse of
+    /// [`to_numeric_paths`](FieldMask::to_numeric_paths).
+    #[cfg(feature = "numeric-paths")]
+    pub fn from_numeric_paths(
+        paths: &[Vec<u32>],
+        descriptor: &impl FieldNumberLookup,
+    ) -> Result<FieldMask, FieldMaskError> {
+        let paths = paths
+            .iter()
+            .map(|numbers| {
+                if numbers.is_empty() {
+                    return Err(FieldMaskError::EmptyPath);
+                }
+                let names = numbers
+                    .iter()
+                    .enumerate()
+                    .map(|(i, number)| {
+                        descriptor
+                            .field_name(&numbers[..i], *number)
+                            .ok_or_else(|| FieldMaskError::UnknownField {
+                                path: numbers
+                                    .iter()
+                                    .map(u32::to_string)
+                                    .collect::<Vec<_>>()
+                                    .join("."),
+                                segment: number.to_string(),
+                            })
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(names.join("."))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(FieldMask(paths))
+    }
+
+    /// Applies an update of `target` with the fields of `source` selected by this mask, where
+    /// both are JSON objects as produced by the proto3 JSON mapping.
+    ///
+    /// Every selected field present in `source` is copied into `target`, and every selected field
+    /// missing from `source` is cleared in `target`. A mask covering [`all`](FieldMask::all)
+    /// fields replaces `target` with `source`.
+    ///
+    /// Fails with the camelCase form of every path which refers to a field in neither `source`
+    /// nor `target`, in which case `target` is left unchanged.
+    pub fn apply_update(&self, target: &mut Value, source: &Value) -> Result<(), Vec<String>> {
+        if self.is_all() {
+            *target = source.clone();
+            return Ok(());
+        }
+        let paths: Vec<_> = self.0.iter().map(|path| json_keys(path)).collect();
+        let unknown: Vec<_> = self
+            .0
+            .iter()
+            .zip(&paths)
+            .filter(|(_, keys)| lookup(source, keys).is_none() && lookup(target, keys).is_none())
+            .map(|(path, _)| {
+                let mut repr = String::new();
+                titlecase(path, &mut repr);
+                repr
+            })
+            .collect();
+        if !unknown.is_empty() {
+            return Err(unknown);
+        }
+        for keys in &paths {
+            match lookup(source, keys) {
+                Some(value) => insert(target, keys, value.clone()),
+                None => remove(target, keys),
+            }
+        }
+        Ok(())
+    }
+}
+
 impl Serialize for FieldMask {
     fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
     where
@@ -48,55 +965,222 @@ impl<'de> Deserialize<'de> for FieldMask {
     where
         D: Deserializer<'de>,
     {
-        let s: &str = Deserialize::deserialize(deserializer)?;
-        Ok(FieldMask::from_str(s).unwrap())
+        let s: String = Deserialize::deserialize(deserializer)?;
+        // an empty mask serializes as an empty string, which has no paths
+        if s.is_empty() {
+            return Ok(FieldMask::default());
+        }
+        Ok(FieldMask::from_str(&s).unwrap())
+    }
+}
+
+impl From<Vec<String>> for FieldMask {
+    fn from(paths: Vec<String>) -> FieldMask {
+        FieldMask::from_paths(paths)
+    }
+}
+
+impl From<&[&str]> for FieldMask {
+    fn from(paths: &[&str]) -> FieldMask {
+        FieldMask::from_paths(paths)
+    }
+}
+
+/// Exposes the stored snake_case paths, so slice methods like `len` and `iter` work directly on
+/// a mask.
+impl std::ops::Deref for FieldMask {
+    type Target = [String];
+
+    fn deref(&self) -> &[String] {
+        &self.0
+    }
+}
+
+impl AsRef<[String]> for FieldMask {
+    fn as_ref(&self) -> &[String] {
+        &self.0
+    }
+}
+
+/// Returns true if `mask` is `None` or has no paths, for omitting both with
+/// `#[serde(skip_serializing_if = "google_apis_common::field_mask::is_none_or_empty")]`.
+// `Option::is_none_or` needs Rust 1.82
+#[allow(clippy::unnecessary_map_or)]
+pub fn is_none_or_empty(mask: &Option<FieldMask>) -> bool {
+    mask.as_ref().map_or(true, FieldMask::is_empty)
+}
+
+/// Splits a comma-separated list of paths, ignoring commas within backtick-quoted segments.
+fn split_paths(s: &str) -> Vec<&str> {
+    let mut in_quotes = false;
+    let mut prev_ind = 0;
+    let mut paths = Vec::new();
+    for (i, c) in s.char_indices() {
+        if c == '`' {
+            in_quotes = !in_quotes;
+        } else if in_quotes {
+            continue;
+        } else if c == ',' {
+            paths.push(&s[prev_ind..i]);
+            prev_ind = i + 1;
+        }
     }
+    paths.push(&s[prev_ind..]);
+    paths
 }
 
 impl FromStr for FieldMask {
     type Err = std::convert::Infallible;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut in_quotes = false;
-        let mut prev_ind = 0;
-        let mut paths = Vec::new();
-        for (i, c) in s.chars().enumerate() {
-            if c == '`' {
-                in_quotes = !in_quotes;
-            } else if in_quotes {
-                continue;
-            } else if c == ',' {
-                paths.push(snakecase(&s[prev_ind..i]));
-                prev_ind = i + 1;
-            }
+        Ok(FieldMask::from_unnormalized_paths(split_paths(s)))
+    }
+}
+
+/// Parses a comma-separated list of paths like [`FromStr`], but validates every path.
+///
+/// An empty string is an empty mask.
+impl TryFrom<&str> for FieldMask {
+    type Error = FieldMaskError;
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        if s.is_empty() {
+            return Ok(FieldMask::default());
+        }
+        let paths: Vec<String> = split_paths(s).into_iter().map(snakecase).collect();
+        for path in &paths {
+            validate_path(path)?;
         }
-        paths.push(snakecase(&s[prev_ind..]));
         Ok(FieldMask(paths))
     }
 }
 
+impl TryFrom<String> for FieldMask {
+    type Error = FieldMaskError;
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        FieldMask::try_from(s.as_str())
+    }
+}
+
+/// Accepts the canonical comma-separated string, as well as an array of paths, and validates
+/// every path like the conversion from `&str`.
+impl TryFrom<&Value> for FieldMask {
+    type Error = FieldMaskError;
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        let kind = |value: &Value| match value {
+            Value::Null => "null",
+            Value::Bool(_) => "boolean",
+            Value::Number(_) => "number",
+            Value::String(_) => "string",
+            Value::Array(_) => "array",
+            Value::Object(_) => "object",
+        };
+        let paths = match value {
+            Value::String(s) => return FieldMask::try_from(s.as_str()),
+            Value::Array(paths) => paths,
+            _ => return Err(FieldMaskError::UnexpectedJson { found: kind(value) }),
+        };
+        let mut mask = Vec::with_capacity(paths.len());
+        for path in paths {
+            let path = path
+                .as_str()
+                .ok_or(FieldMaskError::UnexpectedJson { found: kind(path) })?;
+            let path = snakecase(path);
+            validate_path(&path)?;
+            mask.push(path);
+        }
+        Ok(FieldMask(mask))
+    }
+}
+
 impl Display for FieldMask {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let mut repr = String::new();
-        for path in &self.0 {
-            titlecase(path, &mut repr);
-            repr.push(',');
+        f.write_str(&self.to_camel_case_string())
+    }
+}
+
+/// Builds a [`FieldMask`] in insertion order, see [`FieldMask::ordered_builder`].
+///
+/// Paths are converted to snake_case and exact duplicates are skipped, but they are neither sorted
+/// nor dropped when covered by an ancestor.
+#[derive(Clone, Debug, Default)]
+pub struct OrderedFieldMaskBuilder {
+    paths: Vec<String>,
+}
+
+impl OrderedFieldMaskBuilder {
+    /// Appends `path`, given in either camelCase or snake_case, unless it was added before.
+    pub fn path(mut self, path: impl AsRef<str>) -> Self {
+        let path = snakecase(path.as_ref());
+        if !self.paths.contains(&path) {
+            self.paths.push(path);
         }
-        repr.pop();
-        f.write_str(&repr)
+        self
+    }
+
+    /// Appends all of `paths` like [`path`](OrderedFieldMaskBuilder::path).
+    pub fn paths<I, P>(self, paths: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<str>,
+    {
+        paths.into_iter().fold(self, Self::path)
+    }
+
+    /// Returns the mask with all paths added so far.
+    pub fn build(self) -> FieldMask {
+        FieldMask(self.paths)
     }
 }
 
-#[cfg(test)]
-mod test {
-    use crate::field_mask::FieldMask;
-    use serde::{Deserialize, Serialize};
+/// A read-only `FieldMask` borrowing its paths from the deserialized input.
+///
+/// The paths are kept exactly as they appear on the wire, usually camelCase, as converting them
+/// would require an allocation. Use [`to_owned`](FieldMaskRef::to_owned) to obtain a
+/// [`FieldMask`] with its paths in snake_case.
+///
+/// Deserialization requires the input string to be borrowable, and thus fails for deserializers
+/// which can't provide borrowed strings, or JSON strings containing escape sequences.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FieldMaskRef<'a>(Vec<&'a str>);
 
-    #[derive(Serialize, Deserialize, Debug, PartialEq)]
-    struct FieldMaskWrapper {
-        fields: Option<FieldMask>,
+impl<'a> FieldMaskRef<'a> {
+    /// Parses a comma-separated list of paths, borrowing each path from `s`.
+    pub fn parse(s: &'a str) -> FieldMaskRef<'a> {
+        FieldMaskRef(split_paths(s))
     }
 
-    #[test]
+    /// Returns the paths as they appeared in the input.
+    pub fn paths(&self) -> &[&'a str] {
+        &self.0
+    }
+
+    /// Returns an owned mask with its paths converted to snake_case.
+    pub fn to_owned(&self) -> FieldMask {
+        FieldMask(self.0.iter().copied().map(snakecase).collect())
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for FieldMaskRef<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<FieldMaskRef<'a>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s: &'de str = Deserialize::deserialize(deserializer)?;
+        Ok(FieldMaskRef::parse(s))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::field_mask::{FieldMask, FieldMaskError, FieldMaskRef, FieldNames, ProjectionOrder};
+    use serde::{Deserialize, Serialize};
+    use serde_json::{json, Value};
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct FieldMaskWrapper {
+        fields: Option<FieldMask>,
+    }
+
+    #[test]
     fn field_mask_roundtrip() {
         let wrapper = FieldMaskWrapper {
             fields: Some(FieldMask(vec![
@@ -117,6 +1201,81 @@ mod test {
         );
     }
 
+    #[test]
+    fn bare_field_mask_roundtrip() {
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        #[serde(rename_all = "camelCase")]
+        struct UpdateRequest {
+            update_mask: FieldMask,
+        }
+
+        let request = UpdateRequest {
+            update_mask: FieldMask(vec![
+                "user.display_name".to_string(),
+                "labels.`Some_Key`".to_string(),
+            ]),
+        };
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(
+            json,
+            json!({"updateMask": "user.displayName,labels.`Some_Key`"})
+        );
+        assert_eq!(request, serde_json::from_value(json).unwrap());
+
+        let empty = UpdateRequest {
+            update_mask: FieldMask::default(),
+        };
+        let json = serde_json::to_string(&empty).unwrap();
+        assert_eq!(json, r#"{"updateMask":""}"#);
+        assert_eq!(empty, serde_json::from_str(&json).unwrap());
+    }
+
+    #[test]
+    fn quoted_segments_are_verbatim() {
+        let mask: FieldMask = "labels.`DisplayName`,userName,`a_b`.c_d".parse().unwrap();
+        assert_eq!(
+            mask,
+            FieldMask(vec![
+                "labels.`DisplayName`".to_string(),
+                "user_name".to_string(),
+                "`a_b`.c_d".to_string(),
+            ])
+        );
+        assert_eq!(mask.to_string(), "labels.`DisplayName`,userName,`a_b`.cD");
+    }
+
+    #[test]
+    fn field_mask_ref_borrows_input() {
+        #[derive(Deserialize)]
+        struct Wrapper<'a> {
+            #[serde(borrow)]
+            fields: FieldMaskRef<'a>,
+        }
+
+        let json = r#"{"fields": "user.displayName,`a,b`,photo"}"#;
+        let wrapper: Wrapper = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            wrapper.fields.paths(),
+            &["user.displayName", "`a,b`", "photo"]
+        );
+        let input = json.as_bytes().as_ptr_range();
+        for path in wrapper.fields.paths() {
+            assert!(
+                input.contains(&path.as_ptr()),
+                "{} should point into the input",
+                path
+            );
+        }
+        assert_eq!(
+            wrapper.fields.to_owned(),
+            FieldMask(vec![
+                "user.display_name".to_string(),
+                "`a,b`".to_string(),
+                "photo".to_string()
+            ])
+        );
+    }
+
     #[test]
     fn test_empty_wrapper() {
         assert_eq!(
@@ -124,4 +1283,792 @@ mod test {
             serde_json::from_str("{}").unwrap()
         );
     }
+
+    #[test]
+    fn normalize() {
+        let mut mask = FieldMask(vec![
+            "user.name".to_string(),
+            "photo".to_string(),
+            "user".to_string(),
+            "user_id".to_string(),
+            "photo".to_string(),
+        ]);
+        mask.normalize();
+        assert_eq!(
+            mask,
+            FieldMask(vec![
+                "photo".to_string(),
+                "user".to_string(),
+                "user_id".to_string()
+            ])
+        );
+    }
+
+    #[test]
+    fn normalize_quoted_segments() {
+        let mut mask: FieldMask = "labels.`a.b`,labels,labels.`c`".parse().unwrap();
+        mask.normalize();
+        assert_eq!(mask, FieldMask(vec!["labels".to_string()]));
+
+        let mut mask = FieldMask::for_paths(vec![
+            "labels.`a.b`".to_string(),
+            "labels.`a".to_string(),
+            "labels.`a.b`.c".to_string(),
+        ]);
+        mask.normalize();
+        assert_eq!(
+            mask,
+            FieldMask(vec!["labels.`a".to_string(), "labels.`a.b`".to_string()])
+        );
+        assert!(!mask.contains("labels.`a.c`"));
+        assert!(mask.contains("labels.`a.b`.d"));
+        assert_eq!(mask.strip_prefix("labels.`a.b`"), FieldMask::all());
+        let quoted = FieldMask::for_paths(vec!["labels.`a.b`".to_string()]);
+        assert_eq!(quoted.strip_prefix("labels.`a"), FieldMask::default());
+    }
+
+    #[test]
+    fn canonical_eq() {
+        let a: FieldMask = "user.name,photo,user".parse().unwrap();
+        let b: FieldMask = "photo,user".parse().unwrap();
+        assert!(a.canonical_eq(&b));
+        assert_ne!(a, b);
+
+        let reordered: FieldMask = "user,photo".parse().unwrap();
+        assert!(b.canonical_eq(&reordered));
+        assert_ne!(b, reordered);
+
+        let other: FieldMask = "photo,user.name".parse().unwrap();
+        assert!(!b.canonical_eq(&other));
+        assert!(FieldMask::all().canonical_eq(&"user,*".parse().unwrap()));
+    }
+
+    #[test]
+    fn check_no_redundancy() {
+        let mask: FieldMask = "user,user.name,photo,user.address.city".parse().unwrap();
+        assert_eq!(
+            mask.check_no_redundancy(),
+            Err(vec![
+                "user.name".to_string(),
+                "user.address.city".to_string()
+            ])
+        );
+        let mask: FieldMask = "*,photo".parse().unwrap();
+        assert_eq!(mask.check_no_redundancy(), Err(vec!["photo".to_string()]));
+
+        let mask: FieldMask = "user.name,username,photo".parse().unwrap();
+        assert_eq!(mask.check_no_redundancy(), Ok(()));
+        assert_eq!(FieldMask::all().check_no_redundancy(), Ok(()));
+    }
+
+    #[test]
+    fn sorted_keeps_descendants() {
+        let mask = FieldMask(vec![
+            "user.name".to_string(),
+            "photo".to_string(),
+            "user".to_string(),
+            "photo".to_string(),
+        ]);
+        assert_eq!(
+            mask.sorted(),
+            FieldMask(vec![
+                "photo".to_string(),
+                "user".to_string(),
+                "user.name".to_string()
+            ])
+        );
+    }
+
+    #[test]
+    fn from_vec_of_strings() {
+        let mask = FieldMask::from(vec![
+            "user.displayName".to_string(),
+            "photo".to_string(),
+            "user.displayName".to_string(),
+        ]);
+        assert_eq!(
+            mask,
+            FieldMask(vec!["photo".to_string(), "user.display_name".to_string()])
+        );
+    }
+
+    #[test]
+    fn from_slice_of_strs() {
+        let mask = FieldMask::from(&["user.displayName", "user", "photoUrl"][..]);
+        assert_eq!(
+            mask,
+            FieldMask(vec!["photo_url".to_string(), "user".to_string()])
+        );
+    }
+
+    #[test]
+    fn try_from_json_value() {
+        let expected = FieldMask(vec!["user.display_name".to_string(), "photo".to_string()]);
+        assert_eq!(
+            FieldMask::try_from(&json!("user.displayName,photo")),
+            Ok(expected.clone())
+        );
+        assert_eq!(
+            FieldMask::try_from(&json!(["user.displayName", "photo"])),
+            Ok(expected)
+        );
+        assert_eq!(FieldMask::try_from(&json!([])), Ok(FieldMask::default()));
+
+        assert_eq!(
+            FieldMask::try_from(&json!(42)),
+            Err(FieldMaskError::UnexpectedJson { found: "number" })
+        );
+        assert_eq!(
+            FieldMask::try_from(&json!(["photo", null])),
+            Err(FieldMaskError::UnexpectedJson { found: "null" })
+        );
+        assert!(matches!(
+            FieldMask::try_from(&json!(["photo", "a..b"])),
+            Err(FieldMaskError::InvalidSegment { .. })
+        ));
+    }
+
+    #[test]
+    fn partition_paths() {
+        let mask: FieldMask = "user,photo.url".parse().unwrap();
+        let (covered, uncovered) = mask.partition_paths(&[
+            "user.name",
+            "photo",
+            "user",
+            "photo.url",
+            "username",
+            "photo.url.host",
+        ]);
+        assert_eq!(
+            covered,
+            ["user.name", "user", "photo.url", "photo.url.host"]
+        );
+        assert_eq!(uncovered, ["photo", "username"]);
+
+        let (covered, uncovered) = FieldMask::all().partition_paths(&["a", "b.c"]);
+        assert_eq!(covered, ["a", "b.c"]);
+        assert!(uncovered.is_empty());
+    }
+
+    #[test]
+    fn slice_methods() {
+        let mask: FieldMask = "user.displayName,photo".parse().unwrap();
+        assert_eq!(mask.len(), 2);
+        assert_eq!(mask[0], "user.display_name");
+        assert_eq!(mask.first().map(String::as_str), Some("user.display_name"));
+        assert_eq!(
+            mask.iter().map(String::as_str).collect::<Vec<_>>(),
+            ["user.display_name", "photo"]
+        );
+        assert_eq!(&mask[1..], ["photo".to_string()]);
+        let paths: &[String] = mask.as_ref();
+        assert_eq!(paths.len(), 2);
+    }
+
+    #[test]
+    fn serialized_len() {
+        let mut mask: FieldMask = "user.displayName,photo_url,labels.`a.b_c`,*"
+            .parse()
+            .unwrap();
+        mask.push("items.0.unit_price").unwrap();
+        mask.push("trailing_").unwrap();
+        assert_eq!(mask.serialized_len(), mask.to_string().len());
+        assert_eq!(mask.serialized_len(), 69);
+
+        for mask in [
+            FieldMask::default(),
+            FieldMask::all(),
+            FieldMask::for_paths(vec!["weird_key.a_`b`".to_string(), "x y".to_string()]),
+        ] {
+            assert_eq!(mask.serialized_len(), mask.to_string().len(), "{:?}", mask);
+        }
+    }
+
+    #[test]
+    fn json_keys_to_snake_case() {
+        let mut value = json!({
+            "displayName": "Jane",
+            "homeAddress": {"postalCode": "8000", "address_lines": ["Bahnhofstrasse 1"]},
+            "phoneNumbers": [{"e164Number": "+41440000000"}, "plainString"],
+            "id": 1,
+        });
+        super::json_keys_to_snake_case(&mut value).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "display_name": "Jane",
+                "home_address": {"postal_code": "8000", "address_lines": ["Bahnhofstrasse 1"]},
+                "phone_numbers": [{"e164_number": "+41440000000"}, "plainString"],
+                "id": 1,
+            })
+        );
+
+        // backticks have no meaning in keys
+        let mut value = json!({"`fooBar`": [{"aB": 1}]});
+        super::json_keys_to_snake_case(&mut value).unwrap();
+        assert_eq!(value, json!({"`foo_bar`": [{"a_b": 1}]}));
+
+        // colliding keys are reported, also within arrays
+        let mut value = json!({"items": [{"fooBar": 1, "foo_bar": 2}]});
+        let err = super::json_keys_to_snake_case(&mut value).unwrap_err();
+        assert_eq!(err.key, "foo_bar");
+        // which key comes first depends on the order of the map
+        let mut keys = [err.first.as_str(), err.second.as_str()];
+        keys.sort_unstable();
+        assert_eq!(keys, ["fooBar", "foo_bar"]);
+    }
+
+    #[test]
+    fn segmented() {
+        let mask: FieldMask = "user.displayName,labels.`a.b`.value,photo".parse().unwrap();
+        assert_eq!(
+            mask.segmented().collect::<Vec<_>>(),
+            [
+                vec!["user", "display_name"],
+                vec!["labels", "`a.b`", "value"],
+                vec!["photo"],
+            ]
+        );
+    }
+
+    #[test]
+    fn top_level_fields() {
+        let mask: FieldMask = "user.name,user.email,photo,labels.`a.b`,labels"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            mask.top_level_fields().into_iter().collect::<Vec<_>>(),
+            ["labels", "photo", "user"]
+        );
+        assert!(FieldMask::default().top_level_fields().is_empty());
+    }
+
+    #[test]
+    fn common_prefix() {
+        let mask: FieldMask = "user.name,user.email".parse().unwrap();
+        assert_eq!(mask.common_prefix().as_deref(), Some("user"));
+        let mask: FieldMask = "user.address.city,user.address.zip,user.address"
+            .parse()
+            .unwrap();
+        assert_eq!(mask.common_prefix().as_deref(), Some("user.address"));
+        let mask: FieldMask = "labels.`a.b`.x,labels.`a.b`.y".parse().unwrap();
+        assert_eq!(mask.common_prefix().as_deref(), Some("labels.`a.b`"));
+
+        let mask: FieldMask = "a,b".parse().unwrap();
+        assert_eq!(mask.common_prefix(), None);
+        let mask: FieldMask = "user.name,username".parse().unwrap();
+        assert_eq!(mask.common_prefix(), None);
+        assert_eq!(FieldMask::default().common_prefix(), None);
+        assert_eq!(FieldMask::all().common_prefix(), None);
+    }
+
+    #[test]
+    fn strip_prefix() {
+        let mask = FieldMask(vec![
+            "user.name".to_string(),
+            "user.email".to_string(),
+            "photo".to_string(),
+            "username".to_string(),
+        ]);
+        assert_eq!(
+            mask.strip_prefix("user"),
+            FieldMask(vec!["name".to_string(), "email".to_string()])
+        );
+        assert_eq!(mask.strip_prefix("photo"), FieldMask::all());
+        assert_eq!(mask.strip_prefix("photo.url"), FieldMask::all());
+        assert_eq!(mask.strip_prefix("missing"), FieldMask::default());
+    }
+
+    #[test]
+    fn with_prefix() {
+        let mask: FieldMask = "name,email".parse().unwrap();
+        let rerooted = mask.with_prefix("user");
+        assert_eq!(
+            rerooted,
+            FieldMask(vec!["user.name".to_string(), "user.email".to_string()])
+        );
+        assert_eq!(rerooted.strip_prefix("user"), mask);
+        assert_eq!(
+            FieldMask::all().with_prefix("user.address"),
+            FieldMask(vec!["user.address".to_string()])
+        );
+        assert_eq!(
+            FieldMask::default().with_prefix("user"),
+            FieldMask::default()
+        );
+    }
+
+    #[test]
+    fn contains() {
+        let mask = FieldMask(vec!["user.name".to_string(), "photo".to_string()]);
+        assert!(mask.contains("photo"));
+        assert!(mask.contains("photo.url"));
+        assert!(mask.contains("user.name"));
+        assert!(!mask.contains("user"));
+        assert!(!mask.contains("user.email"));
+        assert!(!mask.contains("photos"));
+        assert!(!mask.is_all());
+    }
+
+    #[test]
+    fn contains_ignore_case() {
+        let mask = FieldMask(vec![
+            "user.display_name".to_string(),
+            "photo".to_string(),
+            "labels.`Team`".to_string(),
+        ]);
+        assert!(mask.contains_ignore_case("user.displayName"));
+        assert!(mask.contains_ignore_case("user.display_name"));
+        assert!(mask.contains_ignore_case("User.DisplayName"));
+        assert!(mask.contains_ignore_case("photo.thumbnailUrl"));
+        assert!(mask.contains_ignore_case("labels.`Team`"));
+        assert!(!mask.contains_ignore_case("labels.`team`"));
+        assert!(!mask.contains_ignore_case("user"));
+        assert!(!mask.contains_ignore_case("user.displayNames"));
+        assert!(!mask.contains("user.displayName"));
+    }
+
+    #[test]
+    fn push() {
+        let mut mask = FieldMask::default();
+        mask.push("user.displayName").unwrap();
+        mask.push("user.display_name").unwrap();
+        mask.push("labels.`Some.Key`").unwrap();
+        mask.push(String::from("*")).unwrap();
+        assert_eq!(
+            mask,
+            FieldMask(vec![
+                "user.display_name".to_string(),
+                "labels.`Some.Key`".to_string(),
+                "*".to_string()
+            ])
+        );
+
+        for (path, err) in [
+            ("", FieldMaskError::EmptyPath),
+            (
+                "user..name",
+                FieldMaskError::InvalidSegment {
+                    path: "user..name".to_string(),
+                    segment: "".to_string(),
+                },
+            ),
+            (
+                "User",
+                FieldMaskError::InvalidSegment {
+                    path: "_user".to_string(),
+                    segment: "_user".to_string(),
+                },
+            ),
+            (
+                "user.na-me",
+                FieldMaskError::InvalidSegment {
+                    path: "user.na-me".to_string(),
+                    segment: "na-me".to_string(),
+                },
+            ),
+            (
+                "labels.`key",
+                FieldMaskError::UnterminatedQuote {
+                    path: "labels.`key".to_string(),
+                },
+            ),
+        ] {
+            assert_eq!(mask.push(path), Err(err), "pushing '{}'", path);
+        }
+        assert_eq!(mask.0.len(), 3);
+    }
+
+    #[test]
+    fn try_from_str() {
+        fn parse(s: &str) -> Result<FieldMask, FieldMaskError> {
+            let mask = FieldMask::try_from(s)?;
+            Ok(mask)
+        }
+
+        assert_eq!(
+            parse("user.displayName,labels.`Some,Key`"),
+            Ok(FieldMask(vec![
+                "user.display_name".to_string(),
+                "labels.`Some,Key`".to_string()
+            ]))
+        );
+        assert_eq!(parse(""), Ok(FieldMask::default()));
+        assert_eq!(
+            FieldMask::try_from("user,,name".to_string()),
+            Err(FieldMaskError::EmptyPath)
+        );
+        assert_eq!(
+            parse("user.na-me"),
+            Err(FieldMaskError::InvalidSegment {
+                path: "user.na-me".to_string(),
+                segment: "na-me".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn case_strings() {
+        let mask: FieldMask = "user.displayName,photo_url,labels.`Some_Key`"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            mask.to_camel_case_string(),
+            "user.displayName,photoUrl,labels.`Some_Key`"
+        );
+        assert_eq!(
+            mask.to_snake_case_string(),
+            "user.display_name,photo_url,labels.`Some_Key`"
+        );
+        assert_eq!(mask.to_string(), mask.to_camel_case_string());
+    }
+
+    #[test]
+    fn apply_update() {
+        let mut target = json!({
+            "name": "users/1",
+            "displayName": "Old",
+            "email": "old@example.com",
+            "labels": {"Team": "a", "keep": "b"},
+        });
+        let source = json!({
+            "displayName": "New",
+            "labels": {"Team": "c"},
+            "address": {"city": "Zurich"},
+        });
+        let mask: FieldMask = "displayName,email,labels.`Team`,address.city"
+            .parse()
+            .unwrap();
+        mask.apply_update(&mut target, &source).unwrap();
+        assert_eq!(
+            target,
+            json!({
+                "name": "users/1",
+                "displayName": "New",
+                "labels": {"Team": "c", "keep": "b"},
+                "address": {"city": "Zurich"},
+            })
+        );
+
+        let mut all = json!({"name": "users/1"});
+        FieldMask::all().apply_update(&mut all, &source).unwrap();
+        assert_eq!(all, source);
+    }
+
+    #[test]
+    fn project_with_order() {
+        let value: Value = serde_json::from_str(
+            r#"{"name": "n", "user": {"email": "e", "age": 3, "id": 1}, "photo": "p"}"#,
+        )
+        .unwrap();
+        let mask: FieldMask = "photo,user.id,user.email,name".parse().unwrap();
+        let source = mask.project_with_order(&value, ProjectionOrder::SourceOrder);
+        let by_mask = mask.project_with_order(&value, ProjectionOrder::MaskOrder);
+        assert_eq!(source, by_mask);
+        assert_eq!(source, mask.project(&value));
+
+        let source = serde_json::to_string(&source).unwrap();
+        let by_mask = serde_json::to_string(&by_mask).unwrap();
+        if cfg!(feature = "preserve-order") {
+            assert_eq!(
+                source,
+                r#"{"name":"n","user":{"email":"e","id":1},"photo":"p"}"#
+            );
+            assert_eq!(
+                by_mask,
+                r#"{"photo":"p","user":{"id":1,"email":"e"},"name":"n"}"#
+            );
+        } else {
+            assert_eq!(
+                source,
+                r#"{"name":"n","photo":"p","user":{"email":"e","id":1}}"#
+            );
+            assert_eq!(source, by_mask);
+        }
+    }
+
+    #[test]
+    fn index_paths() {
+        let value = json!({
+            "name": "orders/1",
+            "items": [
+                {"name": "a", "price": 1},
+                {"name": "b", "price": 2},
+                {"name": "c", "price": 3},
+            ],
+            "labels": {"0": "zero"},
+        });
+        let mask: FieldMask = "items.0.name,items.2".parse().unwrap();
+        assert_eq!(
+            mask.project(&value),
+            json!({"items": [{"name": "a"}, {"name": "c", "price": 3}]})
+        );
+        assert!(mask.contains("items.0.name"));
+        assert!(mask.contains("items.2.price"));
+        assert!(!mask.contains("items.0.price"));
+        assert!(!mask.contains("items.1.name"));
+
+        // index segments only match arrays, map keys need quoting
+        let labels: FieldMask = "labels.0".parse().unwrap();
+        assert_eq!(labels.project(&value), json!({}));
+        let labels: FieldMask = "labels.`0`".parse().unwrap();
+        assert_eq!(labels.project(&value), json!({"labels": {"0": "zero"}}));
+
+        let mut mask = FieldMask::default();
+        mask.push("items.10.name").unwrap();
+        assert_eq!(
+            mask.push("0.name"),
+            Err(FieldMaskError::InvalidSegment {
+                path: "0.name".to_string(),
+                segment: "0".to_string(),
+            })
+        );
+        assert_eq!(FieldMask::all().project(&value), value);
+
+        let mut target = value.clone();
+        let source = json!({"items": [{"name": "x"}]});
+        let mask: FieldMask = "items.0.name".parse().unwrap();
+        mask.apply_update(&mut target, &source).unwrap();
+        assert_eq!(target["items"][0], json!({"name": "x", "price": 1}));
+        assert_eq!(target["items"][1], value["items"][1]);
+    }
+
+    #[test]
+    fn apply_update_unknown_paths() {
+        let original = json!({"displayName": "Old", "user": {"name": "a"}});
+        let mut target = original.clone();
+        let source = json!({"displayName": "New"});
+        let mask: FieldMask = "displayName,phoneNumber,user.fullName".parse().unwrap();
+        assert_eq!(
+            mask.apply_update(&mut target, &source),
+            Err(vec!["phoneNumber".to_string(), "user.fullName".to_string()])
+        );
+        assert_eq!(target, original);
+    }
+
+    #[test]
+    fn snakecase_into_reuses_buffer() {
+        let mut buffer = String::new();
+        for (source, expected) in [
+            ("displayName", "display_name"),
+            ("a", "a"),
+            ("user.photoUrl", "user.photo_url"),
+            ("labels.`SomeKey`", "labels.`SomeKey`"),
+        ] {
+            super::snakecase_into(source, &mut buffer);
+            assert_eq!(buffer, expected);
+        }
+    }
+
+    #[cfg(feature = "numeric-paths")]
+    #[test]
+    fn numeric_paths() {
+        use crate::field_mask::FieldNumberLookup;
+
+        /// `message User { string display_name = 1; Photo photo = 4; }` where
+        /// `message Photo { string url = 2; }`
+        struct UserLookup;
+
+        impl FieldNumberLookup for UserLookup {
+            fn field_number(&self, parent: &[u32], field: &str) -> Option<u32> {
+                match (parent, field) {
+                    ([], "display_name") => Some(1),
+                    ([], "photo") => Some(4),
+                    ([4], "url") => Some(2),
+                    _ => None,
+                }
+            }
+
+            fn field_name(&self, parent: &[u32], number: u32) -> Option<String> {
+                let name = match (parent, number) {
+                    ([], 1) => "display_name",
+                    ([], 4) => "photo",
+                    ([4], 2) => "url",
+                    _ => return None,
+                };
+                Some(name.to_string())
+            }
+        }
+
+        let mask: FieldMask = "displayName,photo.url".parse().unwrap();
+        let numeric = mask.to_numeric_paths(&UserLookup).unwrap();
+        assert_eq!(numeric, vec![vec![1], vec![4, 2]]);
+        assert_eq!(
+            FieldMask::from_numeric_paths(&numeric, &UserLookup),
+            Ok(mask)
+        );
+
+        let unknown: FieldMask = "photo.size".parse().unwrap();
+        assert_eq!(
+            unknown.to_numeric_paths(&UserLookup),
+            Err(FieldMaskError::UnknownField {
+                path: "photo.size".to_string(),
+                segment: "size".to_string(),
+            })
+        );
+        assert!(FieldMask::all().to_numeric_paths(&UserLookup).is_err());
+        assert_eq!(
+            FieldMask::from_numeric_paths(&[vec![4, 7]], &UserLookup),
+            Err(FieldMaskError::UnknownField {
+                path: "4.7".to_string(),
+                segment: "7".to_string(),
+            })
+        );
+        assert_eq!(
+            FieldMask::from_numeric_paths(&[vec![]], &UserLookup),
+            Err(FieldMaskError::EmptyPath)
+        );
+    }
+
+    #[test]
+    fn debug() {
+        let mask: FieldMask = "user.name,photo".parse().unwrap();
+        assert_eq!(
+            format!("{:?}", mask),
+            r#"FieldMask { paths: ["user.name", "photo"] }"#
+        );
+        assert_eq!(
+            format!("{:#?}", mask),
+            "FieldMask {\n    paths: [\n        \"user.name\",\n        \"photo\",\n    ],\n}"
+        );
+    }
+
+    #[test]
+    fn depth() {
+        let flat: FieldMask = "name,email".parse().unwrap();
+        assert_eq!(flat.max_depth(), 1);
+        assert_eq!(flat.depth_of("email"), Some(1));
+
+        let nested: FieldMask = "name,user.address.city,labels.`a.b`".parse().unwrap();
+        assert_eq!(nested.max_depth(), 3);
+        assert_eq!(nested.depth_of("user.address.city"), Some(3));
+        assert_eq!(nested.depth_of("labels.`a.b`"), Some(2));
+        assert_eq!(nested.depth_of("user.address"), None);
+        assert_eq!(FieldMask::default().max_depth(), 0);
+    }
+
+    #[test]
+    fn ordered_builder() {
+        let mask = FieldMask::ordered_builder()
+            .path("user.displayName")
+            .path("photo")
+            .paths(["user.display_name", "user", "photoUrl", "photo"])
+            .build();
+        assert_eq!(
+            mask,
+            FieldMask(vec![
+                "user.display_name".to_string(),
+                "photo".to_string(),
+                "user".to_string(),
+                "photo_url".to_string(),
+            ])
+        );
+
+        let mut normalized = mask.clone();
+        normalized.normalize();
+        assert_eq!(normalized.to_string(), "photo,photoUrl,user");
+        assert!(mask.canonical_eq(&normalized));
+    }
+
+    #[test]
+    fn for_paths() {
+        let mask = FieldMask::for_paths(vec!["display_url".to_string(), "photo".to_string()]);
+        assert_eq!(
+            mask,
+            FieldMask(vec!["display_url".to_string(), "photo".to_string()])
+        );
+        assert!(mask.contains("display_url"));
+        assert_eq!(mask.to_snake_case_string(), "display_url,photo");
+    }
+
+    #[test]
+    fn from_struct_fields() {
+        struct User;
+
+        impl FieldNames for User {
+            fn field_names() -> &'static [&'static str] {
+                &["name", "display_name", "email"]
+            }
+        }
+
+        assert_eq!(
+            FieldMask::from_struct_fields::<User>(),
+            FieldMask(vec![
+                "display_name".to_string(),
+                "email".to_string(),
+                "name".to_string()
+            ])
+        );
+    }
+
+    #[test]
+    fn is_none_or_empty() {
+        #[derive(Serialize, Debug)]
+        #[serde(rename_all = "camelCase")]
+        struct UpdateRequest {
+            #[serde(skip_serializing_if = "super::is_none_or_empty")]
+            update_mask: Option<FieldMask>,
+        }
+
+        for (update_mask, json) in [
+            (None, "{}"),
+            (Some(FieldMask::default()), "{}"),
+            (
+                Some("user.name".parse().unwrap()),
+                r#"{"updateMask":"user.name"}"#,
+            ),
+        ] {
+            let request = UpdateRequest { update_mask };
+            assert_eq!(
+                serde_json::to_string(&request).unwrap(),
+                json,
+                "{:?}",
+                request
+            );
+        }
+    }
+
+    #[test]
+    fn escape_path_segment() {
+        use super::escape_path_segment;
+
+        assert_eq!(escape_path_segment("displayName"), "displayName");
+        assert!(matches!(
+            escape_path_segment("display_name"),
+            std::borrow::Cow::Borrowed(_)
+        ));
+        assert_eq!(escape_path_segment("a.b"), "`a.b`");
+        assert_eq!(escape_path_segment("a,b"), "`a,b`");
+        assert_eq!(escape_path_segment("first name"), "`first name`");
+        assert_eq!(escape_path_segment("a`b"), "`a``b`");
+
+        let mask = FieldMask(vec![
+            "user.first name".to_string(),
+            "labels.`a.b`".to_string(),
+        ]);
+        assert_eq!(mask.to_string(), "user.`first name`,labels.`a.b`");
+
+        // doubled backticks survive a round trip and are un-doubled in JSON keys
+        let mask = FieldMask(vec![format!("labels.{}", escape_path_segment("a`b"))]);
+        let parsed: FieldMask = mask.to_string().parse().unwrap();
+        assert_eq!(parsed, mask);
+        let value = json!({"labels": {"a`b": 1, "a``b": 2, "a": 3}});
+        assert_eq!(parsed.project(&value), json!({"labels": {"a`b": 1}}));
+    }
+
+    #[test]
+    fn all() {
+        let all = FieldMask::all();
+        assert!(all.is_all());
+        assert!(all.contains("anything"));
+        assert!(all.contains("anything.nested"));
+        assert_eq!(all.to_string(), "*");
+        assert_eq!(all, "*".parse().unwrap());
+        assert_eq!(all.strip_prefix("user"), FieldMask::all());
+
+        let mut mask = FieldMask(vec!["user".to_string(), "*".to_string()]);
+        mask.normalize();
+        assert_eq!(mask, all);
+        assert!(!FieldMask::default().contains("anything"));
+    }
 }
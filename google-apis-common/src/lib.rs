@@ -1,6 +1,7 @@
 pub mod auth;
 pub mod field_mask;
 pub mod serde;
+pub mod types;
 pub mod url;
 
 use std::error;
@@ -25,9 +26,16 @@ use serde_json as json;
 use tokio::io::{AsyncRead, AsyncWrite};
 use tokio::time::sleep;
 
+#[doc(hidden)]
+pub use ::serde as __serde;
 pub use auth::{GetToken, NoToken};
+#[cfg(feature = "chrono")]
 pub use chrono;
-pub use field_mask::FieldMask;
+#[cfg(feature = "numeric-paths")]
+pub use field_mask::FieldNumberLookup;
+pub use field_mask::{
+    FieldMask, FieldMaskError, FieldMaskRef, FieldNames, OrderedFieldMaskBuilder, ProjectionOrder,
+};
 pub use serde_with;
 #[cfg(feature = "yup-oauth2")]
 pub use yup_oauth2 as oauth2;
@@ -214,7 +222,7 @@ pub trait Delegate: Send {
     /// # Arguments
     ///
     /// * `is_success` - a true value indicates the operation was successful. If false, you should
-    ///                  discard all values stored during `store_upload_url`.
+    ///   discard all values stored during `store_upload_url`.
     fn finished(&mut self, is_success: bool) {
         let _ = is_success;
     }
@@ -833,7 +841,7 @@ mod test_api {
     fn dyn_delegate_is_send() {
         fn with_send(_x: impl Send) {}
 
-        let mut dd = DefaultDelegate::default();
+        let mut dd = DefaultDelegate;
         let dlg: &mut dyn Delegate = &mut dd;
         with_send(dlg);
     }
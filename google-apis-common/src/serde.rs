@@ -1,3 +1,9 @@
+/// For `google.protobuf.Duration`, a number of seconds with an `s` suffix, like `"1.5s"`.
+///
+/// An `Option<Duration>` field using `Option<Wrapper>` serializes `None` as `null`. To omit the
+/// field instead, as some APIs require, add `#[serde(default, skip_serializing_if =
+/// "Option::is_none")]`, which skips `Wrapper` entirely for `None`.
+#[cfg(feature = "chrono")]
 pub mod duration {
     use serde::{Deserialize, Deserializer};
     use serde_with::{DeserializeAs, SerializeAs};
@@ -6,12 +12,14 @@ pub mod duration {
 
     use chrono::Duration;
 
-    const MAX_SECONDS: i64 = 315576000000i64;
+    pub(super) const MAX_SECONDS: i64 = 315576000000i64;
+    pub(super) const MIN_SECONDS: i64 = -MAX_SECONDS;
 
     #[derive(Debug)]
-    enum ParseDurationError {
+    pub(super) enum ParseDurationError {
         MissingSecondSuffix,
-        NanosTooSmall,
+        EmptyValue,
+        NanosTooSmall { value: String },
         ParseIntError(std::num::ParseIntError),
         SecondOverflow { seconds: i64, max_seconds: i64 },
         SecondUnderflow { seconds: i64, min_seconds: i64 },
@@ -27,9 +35,12 @@ pub mod duration {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
             match self {
                 ParseDurationError::MissingSecondSuffix => write!(f, "'s' suffix was not present"),
-                ParseDurationError::NanosTooSmall => {
-                    write!(f, "more than 9 digits of second precision required")
-                }
+                ParseDurationError::EmptyValue => write!(f, "no number before the 's' suffix"),
+                ParseDurationError::NanosTooSmall { value } => write!(
+                    f,
+                    "more than 9 digits of second precision required (got '{}')",
+                    value
+                ),
                 ParseDurationError::ParseIntError(pie) => write!(f, "{:?}", pie),
                 ParseDurationError::SecondOverflow {
                     seconds,
@@ -53,64 +64,133 @@ pub mod duration {
 
     impl std::error::Error for ParseDurationError {}
 
-    fn duration_from_str(s: &str) -> Result<Duration, ParseDurationError> {
+    pub(super) fn duration_from_str(s: &str) -> Result<Duration, ParseDurationError> {
         // TODO: Test strings like -.s, -0.0s
         let value = match s.strip_suffix('s') {
             None => return Err(ParseDurationError::MissingSecondSuffix),
             Some(v) => v,
         };
+        if value.is_empty() || value == "-" {
+            return Err(ParseDurationError::EmptyValue);
+        }
 
-        let (seconds, nanoseconds) = if let Some((seconds, nanos)) = value.split_once('.') {
-            let is_neg = seconds.starts_with('-');
-            let seconds = i64::from_str(seconds)?;
-            let nano_magnitude = nanos.chars().filter(|c| c.is_ascii_digit()).count() as u32;
-            if nano_magnitude > 9 {
-                // not enough precision to model the remaining digits
-                return Err(ParseDurationError::NanosTooSmall);
-            }
-
-            // u32::from_str prevents negative nanos (eg '0.-12s) -> lossless conversion to i32
-            // 10_u32.pow(...) scales number to appropriate # of nanoseconds
-            let nanos = u32::from_str(nanos)? as i32;
+        // fast path for whole seconds like `3600s`, which are the most common
+        let Some((seconds, nanos)) = value.split_once('.') else {
+            return check_seconds(i64::from_str(value)?).map(Duration::seconds);
+        };
 
-            let mut nanos = nanos * 10_i32.pow(9 - nano_magnitude);
-            if is_neg {
-                nanos = -nanos;
-            }
-            (seconds, nanos)
-        } else {
-            (i64::from_str(value)?, 0)
+        let is_neg = seconds.starts_with('-');
+        let seconds = i64::from_str(seconds)?;
+        // digits beyond nanosecond precision are fine as long as they are all zero
+        let nanos = match nanos.get(..9).zip(nanos.get(9..)) {
+            Some((nanos, rest)) if rest.bytes().all(|b| b == b'0') => nanos,
+            _ => nanos,
         };
+        let nano_magnitude = nanos.chars().filter(|c| c.is_ascii_digit()).count() as u32;
+        if nano_magnitude > 9 {
+            // not enough precision to model the remaining digits
+            return Err(ParseDurationError::NanosTooSmall {
+                value: s.to_string(),
+            });
+        }
+
+        // u32::from_str prevents negative nanos (eg '0.-12s) -> lossless conversion to i32
+        // 10_u32.pow(...) scales number to appropriate # of nanoseconds
+        let nanos = u32::from_str(nanos)? as i32;
 
-        if seconds >= MAX_SECONDS {
+        let mut nanos = nanos * 10_i32.pow(9 - nano_magnitude);
+        if is_neg {
+            nanos = -nanos;
+        }
+        let seconds = check_seconds(seconds)?;
+        Ok(Duration::seconds(seconds) + Duration::nanoseconds(nanos.into()))
+    }
+
+    /// Checks that `seconds` is within the range of `google.protobuf.Duration`.
+    pub(super) fn check_seconds(seconds: i64) -> Result<i64, ParseDurationError> {
+        if seconds > MAX_SECONDS {
             Err(ParseDurationError::SecondOverflow {
                 seconds,
                 max_seconds: MAX_SECONDS,
             })
-        } else if seconds <= -MAX_SECONDS {
+        } else if seconds < MIN_SECONDS {
             Err(ParseDurationError::SecondUnderflow {
                 seconds,
-                min_seconds: -MAX_SECONDS,
+                min_seconds: MIN_SECONDS,
             })
         } else {
-            Ok(Duration::seconds(seconds) + Duration::nanoseconds(nanoseconds.into()))
+            Ok(seconds)
         }
     }
 
+    /// Returns true if `duration` is within the range of `google.protobuf.Duration`.
+    fn in_range(duration: &Duration) -> bool {
+        (MIN_SECONDS..=MAX_SECONDS).contains(&duration.num_seconds())
+    }
+
+    /// Adds two durations, returning `None` if the result is outside of the range of
+    /// `google.protobuf.Duration`.
+    pub fn checked_add(a: &Duration, b: &Duration) -> Option<Duration> {
+        a.checked_add(b).filter(in_range)
+    }
+
+    /// Subtracts `b` from `a`, returning `None` if the result is outside of the range of
+    /// `google.protobuf.Duration`.
+    pub fn checked_sub(a: &Duration, b: &Duration) -> Option<Duration> {
+        a.checked_sub(b).filter(in_range)
+    }
+
+    /// Formats `duration` in seconds, with a fraction of 3, 6 or 9 digits as needed, e.g. `1.500s`
+    /// or `1.000000001s`, which is the canonical form of the proto3 JSON mapping.
+    ///
+    /// Use [`duration_padded`](super::duration_padded) for always nine digits.
     pub fn to_string(duration: &Duration) -> String {
+        let mut s = String::new();
+        write_duration(duration, &mut s).expect("writing to a String doesn't fail");
+        s
+    }
+
+    /// Writes `duration` formatted like [`to_string`] into `w`, e.g. to build a request path or
+    /// body without allocating a `String` for it.
+    pub fn write_duration<W: std::fmt::Write>(duration: &Duration, w: &mut W) -> std::fmt::Result {
         let seconds = duration.num_seconds();
         let nanoseconds = (*duration - Duration::seconds(seconds))
             .num_nanoseconds()
             .expect("absolute number of nanoseconds is less than 1 billion")
             as i32;
-        if nanoseconds != 0 {
-            if seconds == 0 && nanoseconds.is_negative() {
-                format!("-0.{:0>9}s", nanoseconds.abs())
-            } else {
-                format!("{}.{:0>9}s", seconds, nanoseconds.abs())
-            }
+        if nanoseconds == 0 {
+            // an integer zero has no sign, so any zero duration is `0s`
+            return write!(w, "{}s", seconds);
+        }
+        let sign = if seconds == 0 && nanoseconds.is_negative() {
+            "-"
+        } else {
+            ""
+        };
+        let nanos = nanoseconds.abs();
+        let (fraction, width) = if nanos % 1_000_000 == 0 {
+            (nanos / 1_000_000, 3)
+        } else if nanos % 1_000 == 0 {
+            (nanos / 1_000, 6)
         } else {
-            format!("{}s", seconds)
+            (nanos, 9)
+        };
+        write!(
+            w,
+            "{}{}.{:0>width$}s",
+            sign,
+            seconds,
+            fraction,
+            width = width
+        )
+    }
+
+    /// Displays a duration like [`to_string`].
+    struct Formatted<'a>(&'a Duration);
+
+    impl std::fmt::Display for Formatted<'_> {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            write_duration(self.0, f)
         }
     }
 
@@ -121,7 +201,7 @@ pub mod duration {
         where
             S: serde::Serializer,
         {
-            s.serialize_str(&to_string(value))
+            s.collect_str(&Formatted(value))
         }
     }
 
@@ -134,171 +214,3230 @@ pub mod duration {
             duration_from_str(s).map_err(serde::de::Error::custom)
         }
     }
+
+    /// The precision to which a duration is rounded when it is serialized.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub enum Precision {
+        #[default]
+        Nanoseconds,
+        Microseconds,
+        Milliseconds,
+    }
+
+    /// Options for serializing durations, in particular ones computed at runtime whose
+    /// nanoseconds carry more precision than the caller cares about.
+    ///
+    /// Where the precision is known up front, use [`MillisecondWrapper`] or
+    /// [`MicrosecondWrapper`] with `#[serde_as]` instead. These options are for choosing it at
+    /// runtime, by serializing the [`Rounded`] returned by [`rounded`](Self::rounded).
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct DurationSerializeOptions {
+        pub precision: Precision,
+    }
+
+    impl DurationSerializeOptions {
+        /// Rounds `duration` to the configured precision, with ties rounding away from zero.
+        pub fn round(&self, duration: &Duration) -> Duration {
+            match self.precision {
+                Precision::Nanoseconds => *duration,
+                Precision::Microseconds => MicrosecondWrapper::round(duration),
+                Precision::Milliseconds => MillisecondWrapper::round(duration),
+            }
+        }
+
+        /// Formats `duration` like [`to_string`], after rounding it to the configured precision.
+        pub fn to_string(&self, duration: &Duration) -> String {
+            to_string(&self.round(duration))
+        }
+
+        /// Returns `duration` rounded to the configured precision, which serializes and displays
+        /// like [`Wrapper`] does.
+        pub fn rounded(&self, duration: &Duration) -> Rounded {
+            Rounded(self.round(duration))
+        }
+    }
+
+    /// A duration rounded by [`DurationSerializeOptions::rounded`], which serializes as a duration
+    /// string.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct Rounded(Duration);
+
+    impl std::fmt::Display for Rounded {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            write_duration(&self.0, f)
+        }
+    }
+
+    impl serde::Serialize for Rounded {
+        fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            Wrapper::serialize_as(&self.0, s)
+        }
+    }
+
+    /// Like [`Wrapper`], but rounds to a multiple of `NANOS_PER_UNIT` nanoseconds when
+    /// serializing. Use [`MillisecondWrapper`] or [`MicrosecondWrapper`].
+    pub struct RoundedWrapper<const NANOS_PER_UNIT: i64>;
+
+    pub type MillisecondWrapper = RoundedWrapper<1_000_000>;
+    pub type MicrosecondWrapper = RoundedWrapper<1_000>;
+
+    impl<const NANOS_PER_UNIT: i64> RoundedWrapper<NANOS_PER_UNIT> {
+        /// Rounds `duration` to a multiple of `NANOS_PER_UNIT` nanoseconds, with ties rounding
+        /// away from zero.
+        pub fn round(duration: &Duration) -> Duration {
+            let seconds = Duration::seconds(duration.num_seconds());
+            let nanos = (*duration - seconds)
+                .num_nanoseconds()
+                .expect("absolute number of nanoseconds is less than 1 billion");
+            let rounded = (nanos.abs() + NANOS_PER_UNIT / 2) / NANOS_PER_UNIT * NANOS_PER_UNIT;
+            seconds + Duration::nanoseconds(rounded * nanos.signum())
+        }
+    }
+
+    impl<const NANOS_PER_UNIT: i64> SerializeAs<Duration> for RoundedWrapper<NANOS_PER_UNIT> {
+        fn serialize_as<S>(value: &Duration, s: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            Wrapper::serialize_as(&Self::round(value), s)
+        }
+    }
+
+    impl<'de, const NANOS_PER_UNIT: i64> DeserializeAs<'de, Duration>
+        for RoundedWrapper<NANOS_PER_UNIT>
+    {
+        fn deserialize_as<D>(deserializer: D) -> Result<Duration, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            Wrapper::deserialize_as(deserializer)
+        }
+    }
 }
 
-pub mod urlsafe_base64 {
-    use serde::{Deserialize, Deserializer, Serializer};
+/// Like [`duration`], but clamps values outside of the range representable by
+/// `google.protobuf.Duration` (±315,576,000,000 seconds) instead of failing.
+///
+/// This is lossy: an out-of-range value is silently replaced by the nearest boundary, both when
+/// serializing and when deserializing, so the original value cannot be recovered.
+#[cfg(feature = "chrono")]
+pub mod duration_clamped {
+    use serde::{Deserialize, Deserializer};
     use serde_with::{DeserializeAs, SerializeAs};
+    use std::num::IntErrorKind;
 
-    pub struct Wrapper;
+    use chrono::Duration;
 
-    pub fn to_string(bytes: &Vec<u8>) -> String {
-        base64::encode_config(bytes, base64::URL_SAFE)
+    use super::duration::{self, ParseDurationError, MAX_SECONDS, MIN_SECONDS};
+
+    /// Clamps `duration` to the range representable by `google.protobuf.Duration`.
+    pub fn clamp(duration: &Duration) -> Duration {
+        let max = Duration::seconds(MAX_SECONDS);
+        let min = -max;
+        *duration.clamp(&min, &max)
     }
 
-    impl SerializeAs<Vec<u8>> for Wrapper {
-        fn serialize_as<S>(value: &Vec<u8>, s: S) -> Result<S::Ok, S::Error>
+    pub fn to_string(duration: &Duration) -> String {
+        duration::to_string(&clamp(duration))
+    }
+
+    fn duration_from_str(s: &str) -> Result<Duration, ParseDurationError> {
+        match duration::duration_from_str(s) {
+            Ok(duration) => Ok(clamp(&duration)),
+            Err(ParseDurationError::SecondOverflow { .. }) => Ok(Duration::seconds(MAX_SECONDS)),
+            Err(ParseDurationError::SecondUnderflow { .. }) => Ok(Duration::seconds(MIN_SECONDS)),
+            Err(ParseDurationError::ParseIntError(pie)) => match pie.kind() {
+                IntErrorKind::PosOverflow => Ok(Duration::seconds(MAX_SECONDS)),
+                IntErrorKind::NegOverflow => Ok(Duration::seconds(MIN_SECONDS)),
+                _ => Err(ParseDurationError::ParseIntError(pie)),
+            },
+            Err(err) => Err(err),
+        }
+    }
+
+    pub struct Wrapper;
+
+    impl SerializeAs<Duration> for Wrapper {
+        fn serialize_as<S>(value: &Duration, s: S) -> Result<S::Ok, S::Error>
         where
-            S: Serializer,
+            S: serde::Serializer,
         {
             s.serialize_str(&to_string(value))
         }
     }
 
-    impl<'de> DeserializeAs<'de, Vec<u8>> for Wrapper {
-        fn deserialize_as<D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+    impl<'de> DeserializeAs<'de, Duration> for Wrapper {
+        fn deserialize_as<D>(deserializer: D) -> Result<Duration, D::Error>
         where
             D: Deserializer<'de>,
         {
-            let s: &str = Deserialize::deserialize(deserializer)?;
-            base64::decode_config(s, base64::URL_SAFE).map_err(serde::de::Error::custom)
+            let s = Deserialize::deserialize(deserializer)?;
+            duration_from_str(s).map_err(serde::de::Error::custom)
         }
     }
 }
 
-pub fn datetime_to_string(datetime: &chrono::DateTime<chrono::offset::Utc>) -> String {
-    datetime.to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
-}
+/// Like [`duration`], but serializes non-zero fractions with all nine digits, e.g. `1.500000000s`
+/// instead of the canonical `1.500s`, which some golden-file tests written against older versions
+/// of this crate depend on.
+#[cfg(feature = "chrono")]
+pub mod duration_padded {
+    use serde::Deserializer;
+    use serde_with::{DeserializeAs, SerializeAs};
 
-#[cfg(test)]
-mod test {
-    use super::{duration, urlsafe_base64};
-    use serde::{Deserialize, Serialize};
-    use serde_with::{serde_as, DisplayFromStr};
+    use chrono::Duration;
 
-    #[serde_as]
-    #[derive(Serialize, Deserialize, Debug, PartialEq)]
-    struct DurationWrapper {
-        #[serde_as(as = "Option<duration::Wrapper>")]
-        duration: Option<chrono::Duration>,
-    }
+    use super::duration;
 
-    #[serde_as]
-    #[derive(Serialize, Deserialize, Debug, PartialEq)]
-    struct Base64Wrapper {
-        #[serde_as(as = "Option<urlsafe_base64::Wrapper>")]
-        bytes: Option<Vec<u8>>,
+    /// Formats `duration` in seconds, with all nine fractional digits unless it is a whole number
+    /// of seconds, like `1.500000000s` and `2s`.
+    pub fn to_string(duration: &Duration) -> String {
+        let mut s = String::new();
+        write_duration(duration, &mut s).expect("writing to a String doesn't fail");
+        s
     }
 
-    #[serde_as]
-    #[derive(Serialize, Deserialize, Debug, PartialEq)]
-    struct I64Wrapper {
-        #[serde_as(as = "Option<DisplayFromStr>")]
-        num: Option<i64>,
+    /// Writes `duration` formatted like [`to_string`] into `w`.
+    pub fn write_duration<W: std::fmt::Write>(duration: &Duration, w: &mut W) -> std::fmt::Result {
+        let seconds = duration.num_seconds();
+        let nanoseconds = (*duration - Duration::seconds(seconds))
+            .num_nanoseconds()
+            .expect("absolute number of nanoseconds is less than 1 billion")
+            as i32;
+        if nanoseconds == 0 {
+            // an integer zero has no sign, so any zero duration is `0s`
+            write!(w, "{}s", seconds)
+        } else if seconds == 0 && nanoseconds.is_negative() {
+            write!(w, "-0.{:0>9}s", nanoseconds.abs())
+        } else {
+            write!(w, "{}.{:0>9}s", seconds, nanoseconds.abs())
+        }
     }
 
-    #[test]
-    fn test_duration_de_success_cases() {
-        let durations = [
-            ("-0.2s", -200_000_000),
-            ("0.000000001s", 1),
-            ("999.999999999s", 999_999_999_999),
-            ("129s", 129_000_000_000),
-            ("0.123456789s", 123_456_789),
-        ];
-        for (repr, nanos) in durations.into_iter() {
-            let wrapper: DurationWrapper =
-                serde_json::from_str(&format!("{{\"duration\": \"{}\"}}", repr)).unwrap();
-            assert_eq!(
-                Some(nanos),
-                wrapper.duration.unwrap().num_nanoseconds(),
-                "parsed \"{}\" expecting Duration with {}ns",
-                repr,
-                nanos
-            );
+    pub struct Wrapper;
+
+    impl SerializeAs<Duration> for Wrapper {
+        fn serialize_as<S>(value: &Duration, s: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            s.serialize_str(&to_string(value))
         }
     }
 
-    #[test]
-    fn test_duration_de_failure_cases() {
-        let durations = ["1.-3s", "1.1111111111s", "1.2"];
-        for repr in durations.into_iter() {
-            assert!(
-                serde_json::from_str::<DurationWrapper>(&format!("{{\"duration\": \"{}\"}}", repr))
-                    .is_err(),
-                "parsed \"{}\" expecting err",
-                repr
-            );
+    impl<'de> DeserializeAs<'de, Duration> for Wrapper {
+        fn deserialize_as<D>(deserializer: D) -> Result<Duration, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            duration::Wrapper::deserialize_as(deserializer)
         }
     }
+}
 
-    #[test]
-    fn test_duration_ser_success_cases() {
-        let durations = [
-            -200_000_000,
-            1,
-            999_999_999_999,
-            129_000_000_000,
-            123_456_789,
-        ];
+/// For durations given as their total number of nanoseconds, as an int64 string like
+/// `"1500000000"`.
+///
+/// Integers are accepted on deserialization as well. Serialization fails for durations of more
+/// than `i64::MAX` nanoseconds, roughly 292 years.
+#[cfg(feature = "chrono")]
+pub mod duration_nanos {
+    use serde::{Deserializer, Serializer};
+    use serde_with::{DeserializeAs, SerializeAs};
 
-        for nanos in durations.into_iter() {
-            let wrapper = DurationWrapper {
-                duration: Some(chrono::Duration::nanoseconds(nanos)),
-            };
-            let s = serde_json::to_string(&wrapper);
-            assert!(s.is_ok(), "Could not serialize {}ns", nanos);
-            let s = s.unwrap();
-            assert_eq!(
-                wrapper,
-                serde_json::from_str(&s).unwrap(),
-                "round trip should return same duration"
-            );
+    use chrono::Duration;
+
+    use super::str_like;
+
+    pub struct Wrapper;
+
+    impl SerializeAs<Duration> for Wrapper {
+        fn serialize_as<S>(value: &Duration, s: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            let nanos = value.num_nanoseconds().ok_or_else(|| {
+                serde::ser::Error::custom(format!(
+                    "duration of {}s overflows int64 nanoseconds",
+                    value.num_seconds()
+                ))
+            })?;
+            s.collect_str(&nanos)
         }
     }
 
-    #[test]
-    fn urlsafe_base64_de_success_cases() {
-        let wrapper: Base64Wrapper =
-            serde_json::from_str(r#"{"bytes": "aGVsbG8gd29ybGQ="}"#).unwrap();
-        assert_eq!(Some(b"hello world".as_slice()), wrapper.bytes.as_deref());
+    impl<'de> DeserializeAs<'de, Duration> for Wrapper {
+        fn deserialize_as<D>(deserializer: D) -> Result<Duration, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let nanos: i64 = str_like::Wrapper::deserialize_as(deserializer)?;
+            Ok(Duration::nanoseconds(nanos))
+        }
     }
+}
 
-    #[test]
-    fn urlsafe_base64_de_failure_cases() {
-        assert!(serde_json::from_str::<Base64Wrapper>(r#"{"bytes": "aGVsbG8gd29ybG+Q"}"#).is_err());
-    }
+/// For a `google.protobuf.Duration` in the form of its message, `{"seconds": "123", "nanos": 456}`,
+/// in all formats, mirroring the binary encoding rather than the string of the JSON mapping.
+///
+/// `seconds` is an int64 string, which is also accepted as a number, and `nanos` an int32. Both
+/// default to 0, and on deserialization must not have opposite signs.
+#[cfg(feature = "chrono")]
+pub mod duration_struct {
+    use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+    use serde_with::{serde_as, DeserializeAs, SerializeAs};
 
-    #[test]
-    fn urlsafe_base64_roundtrip() {
-        let wrapper = Base64Wrapper {
-            bytes: Some(b"Hello world!".to_vec()),
-        };
-        let s = serde_json::to_string(&wrapper).expect("serialization of bytes infallible");
-        assert_eq!(wrapper, serde_json::from_str::<Base64Wrapper>(&s).unwrap());
+    use chrono::Duration;
+
+    use super::{duration, str_like};
+
+    #[serde_as]
+    #[derive(Serialize, Deserialize)]
+    struct DurationRepr {
+        #[serde_as(as = "str_like::Wrapper")]
+        #[serde(default)]
+        seconds: i64,
+        #[serde(default)]
+        nanos: i32,
     }
 
-    #[test]
-    fn num_roundtrip() {
-        let wrapper = I64Wrapper {
-            num: Some(i64::MAX),
-        };
+    pub struct Wrapper;
 
-        let json_repr = &serde_json::to_string(&wrapper);
-        assert!(json_repr.is_ok(), "serialization should succeed");
-        assert_eq!(
-            wrapper,
-            serde_json::from_str(&format!("{{\"num\": \"{}\"}}", i64::MAX)).unwrap()
-        );
-        assert_eq!(
-            wrapper,
-            serde_json::from_str(json_repr.as_ref().unwrap()).unwrap(),
-            "round trip should succeed"
+    impl SerializeAs<Duration> for Wrapper {
+        fn serialize_as<S>(value: &Duration, s: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            let seconds = value.num_seconds();
+            let nanos = (*value - Duration::seconds(seconds))
+                .num_nanoseconds()
+                .expect("absolute number of nanoseconds is less than 1 billion")
+                as i32;
+            DurationRepr { seconds, nanos }.serialize(s)
+        }
+    }
+
+    impl<'de> DeserializeAs<'de, Duration> for Wrapper {
+        fn deserialize_as<D>(deserializer: D) -> Result<Duration, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let DurationRepr { seconds, nanos } = DurationRepr::deserialize(deserializer)?;
+            duration::check_seconds(seconds).map_err(de::Error::custom)?;
+            if !(-999_999_999..=999_999_999).contains(&nanos) {
+                return Err(de::Error::custom(format!(
+                    "nanos must be within +-999999999 (got {})",
+                    nanos
+                )));
+            }
+            if seconds.signum() * i64::from(nanos.signum()) < 0 {
+                return Err(de::Error::custom(format!(
+                    "seconds and nanos must have the same sign (got {} and {})",
+                    seconds, nanos
+                )));
+            }
+            Ok(Duration::seconds(seconds) + Duration::nanoseconds(nanos.into()))
+        }
+    }
+}
+
+/// Like [`duration`], but additionally accepts integers without the `s` suffix as seconds, like
+/// the `"0"` some producers emit for a zero duration.
+///
+/// Serialization is unchanged, and always includes the suffix.
+#[cfg(feature = "chrono")]
+pub mod duration_lenient {
+    use serde::{Deserialize, Deserializer};
+    use serde_with::{DeserializeAs, SerializeAs};
+    use std::borrow::Cow;
+
+    use chrono::Duration;
+
+    use super::duration::{self, ParseDurationError};
+
+    fn duration_from_str(s: &str) -> Result<Duration, ParseDurationError> {
+        let s = match s.parse::<i64>() {
+            Ok(_) => Cow::Owned(format!("{}s", s)),
+            Err(_) => Cow::Borrowed(s),
+        };
+        duration::duration_from_str(&s)
+    }
+
+    pub struct Wrapper;
+
+    impl SerializeAs<Duration> for Wrapper {
+        fn serialize_as<S>(value: &Duration, s: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            duration::Wrapper::serialize_as(value, s)
+        }
+    }
+
+    impl<'de> DeserializeAs<'de, Duration> for Wrapper {
+        fn deserialize_as<D>(deserializer: D) -> Result<Duration, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let s = Deserialize::deserialize(deserializer)?;
+            duration_from_str(s).map_err(serde::de::Error::custom)
+        }
+    }
+}
+
+/// Like [`duration`], but additionally accepts a JSON integer as a number of seconds, like the
+/// `129` found in some fixtures for `"129s"`, for compatibility layers.
+///
+/// A bare number is ambiguous, as some producers count milliseconds instead, so this module should
+/// only be used where seconds are known to be meant. Strings must still carry the `s` suffix, and
+/// serialization is unchanged.
+#[cfg(feature = "chrono")]
+pub mod duration_compat {
+    use serde::{de, Deserializer};
+    use serde_with::{DeserializeAs, SerializeAs};
+    use std::fmt::Formatter;
+
+    use chrono::Duration;
+
+    use super::duration;
+
+    pub struct Wrapper;
+
+    impl SerializeAs<Duration> for Wrapper {
+        fn serialize_as<S>(value: &Duration, s: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            duration::Wrapper::serialize_as(value, s)
+        }
+    }
+
+    struct Visitor;
+
+    impl<'de> de::Visitor<'de> for Visitor {
+        type Value = Duration;
+
+        fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+            formatter.write_str("a duration string or an integer number of seconds")
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Duration, E> {
+            duration::duration_from_str(v).map_err(E::custom)
+        }
+
+        fn visit_i64<E: de::Error>(self, v: i64) -> Result<Duration, E> {
+            // goes through the parser for its range check
+            self.visit_str(&format!("{}s", v))
+        }
+
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<Duration, E> {
+            self.visit_str(&format!("{}s", v))
+        }
+    }
+
+    impl<'de> DeserializeAs<'de, Duration> for Wrapper {
+        fn deserialize_as<D>(deserializer: D) -> Result<Duration, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_any(Visitor)
+        }
+    }
+}
+
+/// For `google.protobuf.Timestamp`, an RFC 3339 date time.
+///
+/// Any UTC offset is accepted and normalized to UTC, as are lowercase `t` and `z`. Serialization
+/// always uses the `Z` suffix and as many fractional digits as needed, in groups of 0, 3, 6 or 9.
+#[cfg(feature = "chrono")]
+pub mod timestamp {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use serde_with::{DeserializeAs, SerializeAs};
+    use std::fmt::Formatter;
+
+    use chrono::{DateTime, Utc};
+
+    #[derive(Debug)]
+    pub(super) enum ParseTimestampError {
+        InvalidSeparator,
+        ParseError(chrono::ParseError),
+    }
+
+    impl From<chrono::ParseError> for ParseTimestampError {
+        fn from(pe: chrono::ParseError) -> Self {
+            ParseTimestampError::ParseError(pe)
+        }
+    }
+
+    impl std::fmt::Display for ParseTimestampError {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            match self {
+                ParseTimestampError::InvalidSeparator => {
+                    write!(f, "date and time must be separated by 'T'")
+                }
+                ParseTimestampError::ParseError(pe) => write!(f, "{}", pe),
+            }
+        }
+    }
+
+    impl std::error::Error for ParseTimestampError {}
+
+    pub(super) fn timestamp_from_str(s: &str) -> Result<DateTime<Utc>, ParseTimestampError> {
+        // chrono also accepts a space as separator, which the proto spec doesn't permit
+        if s.as_bytes().get(10) == Some(&b' ') {
+            return Err(ParseTimestampError::InvalidSeparator);
+        }
+        Ok(DateTime::parse_from_rfc3339(s)?.with_timezone(&Utc))
+    }
+
+    pub fn to_string(timestamp: &DateTime<Utc>) -> String {
+        timestamp.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true)
+    }
+
+    /// A `google.protobuf.Timestamp` which implements `Serialize` and `Deserialize` itself, for use
+    /// in fields without a `serde_as` attribute.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct Timestamp(pub DateTime<Utc>);
+
+    impl std::ops::Deref for Timestamp {
+        type Target = DateTime<Utc>;
+
+        fn deref(&self) -> &Self::Target {
+            &self.0
+        }
+    }
+
+    impl From<DateTime<Utc>> for Timestamp {
+        fn from(timestamp: DateTime<Utc>) -> Self {
+            Timestamp(timestamp)
+        }
+    }
+
+    impl From<Timestamp> for DateTime<Utc> {
+        fn from(timestamp: Timestamp) -> Self {
+            timestamp.0
+        }
+    }
+
+    impl std::fmt::Display for Timestamp {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            f.write_str(&to_string(&self.0))
+        }
+    }
+
+    impl serde::Serialize for Timestamp {
+        fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            Wrapper::serialize_as(&self.0, s)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Timestamp {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            Wrapper::deserialize_as(deserializer).map(Timestamp)
+        }
+    }
+
+    pub struct Wrapper;
+
+    impl SerializeAs<DateTime<Utc>> for Wrapper {
+        fn serialize_as<S>(value: &DateTime<Utc>, s: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            s.serialize_str(&to_string(value))
+        }
+    }
+
+    impl<'de> DeserializeAs<'de, DateTime<Utc>> for Wrapper {
+        fn deserialize_as<D>(deserializer: D) -> Result<DateTime<Utc>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let s: &str = Deserialize::deserialize(deserializer)?;
+            timestamp_from_str(s).map_err(serde::de::Error::custom)
+        }
+    }
+}
+
+/// Like [`timestamp`], but additionally accepts a space instead of the `T` separating date and
+/// time, like `2020-01-01 00:00:00Z`.
+///
+/// Serialization is unchanged, and always yields strict RFC 3339 with `T` and `Z`.
+#[cfg(feature = "chrono")]
+pub mod timestamp_lenient {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use serde_with::{DeserializeAs, SerializeAs};
+
+    use chrono::{DateTime, Utc};
+
+    use super::timestamp::{self, ParseTimestampError};
+
+    fn timestamp_from_str(s: &str) -> Result<DateTime<Utc>, ParseTimestampError> {
+        if s.as_bytes().get(10) == Some(&b' ') {
+            let s = format!("{}T{}", &s[..10], &s[11..]);
+            return timestamp::timestamp_from_str(&s);
+        }
+        timestamp::timestamp_from_str(s)
+    }
+
+    pub struct Wrapper;
+
+    impl SerializeAs<DateTime<Utc>> for Wrapper {
+        fn serialize_as<S>(value: &DateTime<Utc>, s: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            timestamp::Wrapper::serialize_as(value, s)
+        }
+    }
+
+    impl<'de> DeserializeAs<'de, DateTime<Utc>> for Wrapper {
+        fn deserialize_as<D>(deserializer: D) -> Result<DateTime<Utc>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let s: &str = Deserialize::deserialize(deserializer)?;
+            timestamp_from_str(s).map_err(serde::de::Error::custom)
+        }
+    }
+}
+
+/// For a `google.protobuf.Timestamp` given as a JSON number of seconds since the Unix epoch, as
+/// used by some logging and metrics pipelines instead of the RFC 3339 form of [`timestamp`].
+///
+/// Whole seconds are serialized as integers, and timestamps with a fraction as floats. As a float
+/// only keeps about microsecond precision for current dates, fractions are rounded to whole
+/// microseconds, both when serializing and when deserializing, so that values round-trip exactly.
+#[cfg(feature = "chrono")]
+pub mod timestamp_unix {
+    use serde::{de, Deserializer, Serializer};
+    use serde_with::{DeserializeAs, SerializeAs};
+    use std::fmt::Formatter;
+
+    use chrono::{DateTime, TimeZone, Utc};
+
+    pub struct Wrapper;
+
+    impl SerializeAs<DateTime<Utc>> for Wrapper {
+        fn serialize_as<S>(value: &DateTime<Utc>, s: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            let micros = i128::from(value.timestamp()) * 1_000_000
+                + i128::from((value.timestamp_subsec_nanos() + 500) / 1_000);
+            if micros % 1_000_000 == 0 {
+                return s.serialize_i64((micros / 1_000_000) as i64);
+            }
+            // parsing the decimal gives the float closest to it, unlike adding up the parts
+            let sign = if micros < 0 { "-" } else { "" };
+            let abs = micros.unsigned_abs();
+            let seconds: f64 = format!("{}{}.{:06}", sign, abs / 1_000_000, abs % 1_000_000)
+                .parse()
+                .expect("a decimal number is a valid float");
+            s.serialize_f64(seconds)
+        }
+    }
+
+    struct Visitor;
+
+    impl Visitor {
+        fn from_parts<E: de::Error>(seconds: i64, nanos: u32) -> Result<DateTime<Utc>, E> {
+            Utc.timestamp_opt(seconds, nanos)
+                .single()
+                .ok_or_else(|| E::custom("timestamp out of range"))
+        }
+    }
+
+    impl<'de> de::Visitor<'de> for Visitor {
+        type Value = DateTime<Utc>;
+
+        fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+            formatter.write_str("a number of seconds since the Unix epoch")
+        }
+
+        fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+            Self::from_parts(v, 0)
+        }
+
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+            let seconds = i64::try_from(v).map_err(|_| E::custom("timestamp out of range"))?;
+            Self::from_parts(seconds, 0)
+        }
+
+        fn visit_f64<E: de::Error>(self, v: f64) -> Result<Self::Value, E> {
+            if !v.is_finite() {
+                return Err(E::invalid_value(de::Unexpected::Float(v), &self));
+            }
+            let seconds = v.floor();
+            let micros = ((v - seconds) * 1e6).round() as u32;
+            // the fraction may round up to a full second
+            let (seconds, micros) = match micros {
+                1_000_000 => (seconds as i64 + 1, 0),
+                micros => (seconds as i64, micros),
+            };
+            Self::from_parts(seconds, micros * 1_000)
+        }
+    }
+
+    impl<'de> DeserializeAs<'de, DateTime<Utc>> for Wrapper {
+        fn deserialize_as<D>(deserializer: D) -> Result<DateTime<Utc>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_any(Visitor)
+        }
+    }
+}
+
+/// Like [`str_like`], but for `map<string, int64>` and similar fields, where every value of the
+/// JSON object is serialized as a string.
+///
+/// Parsing errors name the key of the malformed value.
+pub mod str_like_map {
+    use serde::{de, Deserializer, Serializer};
+    use serde_with::de::DeserializeAsWrap;
+    use serde_with::{DeserializeAs, SerializeAs};
+    use std::collections::HashMap;
+    use std::fmt::{Display, Formatter};
+    use std::marker::PhantomData;
+    use std::str::FromStr;
+
+    use super::str_like;
+
+    pub struct Wrapper;
+
+    impl<T: Display> SerializeAs<HashMap<String, T>> for Wrapper {
+        fn serialize_as<S>(value: &HashMap<String, T>, s: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            s.collect_map(value.iter().map(|(key, v)| (key, v.to_string())))
+        }
+    }
+
+    struct Visitor<T>(PhantomData<T>);
+
+    impl<'de, T> de::Visitor<'de> for Visitor<T>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        type Value = HashMap<String, T>;
+
+        fn expecting(&self, f: &mut Formatter) -> std::fmt::Result {
+            write!(f, "a map of strings or numbers")
+        }
+
+        fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+        where
+            A: de::MapAccess<'de>,
+        {
+            let mut values = HashMap::with_capacity(map.size_hint().unwrap_or(0));
+            while let Some(key) = map.next_key::<String>()? {
+                let value = map
+                    .next_value::<DeserializeAsWrap<T, str_like::Wrapper>>()
+                    .map_err(|err| de::Error::custom(format!("key '{}': {}", key, err)))?;
+                values.insert(key, value.into_inner());
+            }
+            Ok(values)
+        }
+    }
+
+    impl<'de, T> DeserializeAs<'de, HashMap<String, T>> for Wrapper
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        fn deserialize_as<D>(deserializer: D) -> Result<HashMap<String, T>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_map(Visitor(PhantomData))
+        }
+    }
+}
+
+/// For a [`FieldMask`](crate::FieldMask) given either in its canonical form, a comma-separated
+/// string of paths, or as a JSON array of paths, which some tools emit.
+///
+/// Paths are converted to snake_case in both cases, and serialization always yields the
+/// canonical string.
+pub mod field_mask_array {
+    use serde::{de, Deserializer, Serialize, Serializer};
+    use serde_with::{DeserializeAs, SerializeAs};
+    use std::fmt::Formatter;
+
+    use crate::FieldMask;
+
+    pub struct Wrapper;
+
+    impl SerializeAs<FieldMask> for Wrapper {
+        fn serialize_as<S>(value: &FieldMask, s: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            value.serialize(s)
+        }
+    }
+
+    struct Visitor;
+
+    impl<'de> de::Visitor<'de> for Visitor {
+        type Value = FieldMask;
+
+        fn expecting(&self, f: &mut Formatter) -> std::fmt::Result {
+            write!(f, "a comma-separated string or an array of paths")
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<FieldMask, E> {
+            if v.is_empty() {
+                return Ok(FieldMask::default());
+            }
+            Ok(v.parse().unwrap())
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<FieldMask, A::Error>
+        where
+            A: de::SeqAccess<'de>,
+        {
+            let mut paths = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+            while let Some(path) = seq.next_element::<String>()? {
+                paths.push(path);
+            }
+            Ok(FieldMask::from_unnormalized_paths(paths))
+        }
+    }
+
+    impl<'de> DeserializeAs<'de, FieldMask> for Wrapper {
+        fn deserialize_as<D>(deserializer: D) -> Result<FieldMask, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_any(Visitor)
+        }
+    }
+}
+
+/// For `google.protobuf.ListValue`, a JSON array of arbitrary `google.protobuf.Value`s, which are
+/// represented by [`serde_json::Value`].
+///
+/// Unlike deserializing into a `serde_json::Value`, anything but an array is rejected.
+pub mod list_value {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use serde_json::Value;
+    use serde_with::{DeserializeAs, SerializeAs};
+
+    pub struct Wrapper;
+
+    impl SerializeAs<Vec<Value>> for Wrapper {
+        fn serialize_as<S>(value: &Vec<Value>, s: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            s.collect_seq(value)
+        }
+    }
+
+    impl<'de> DeserializeAs<'de, Vec<Value>> for Wrapper {
+        fn deserialize_as<D>(deserializer: D) -> Result<Vec<Value>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            Deserialize::deserialize(deserializer)
+        }
+    }
+}
+
+pub mod urlsafe_base64 {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use serde_with::{DeserializeAs, SerializeAs};
+    use std::fmt::Formatter;
+
+    pub struct Wrapper;
+
+    pub fn to_string(bytes: &Vec<u8>) -> String {
+        base64::encode_config(bytes, base64::URL_SAFE)
+    }
+
+    #[derive(Debug)]
+    pub(super) struct DecodeError {
+        error: base64::DecodeError,
+        standard_alphabet: bool,
+    }
+
+    impl std::fmt::Display for DecodeError {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}", self.error)?;
+            if self.standard_alphabet {
+                write!(
+                    f,
+                    " (the input contains '+' or '/' and may be standard base64, \
+                     which the `standard_base64` module decodes)"
+                )?;
+            }
+            Ok(())
+        }
+    }
+
+    impl std::error::Error for DecodeError {}
+
+    pub(super) fn decode(s: &str) -> Result<Vec<u8>, DecodeError> {
+        base64::decode_config(s, base64::URL_SAFE).map_err(|error| DecodeError {
+            standard_alphabet: s.contains(['+', '/']),
+            error,
+        })
+    }
+
+    impl SerializeAs<Vec<u8>> for Wrapper {
+        fn serialize_as<S>(value: &Vec<u8>, s: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            s.serialize_str(&to_string(value))
+        }
+    }
+
+    impl<'de> DeserializeAs<'de, Vec<u8>> for Wrapper {
+        fn deserialize_as<D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let s: &str = Deserialize::deserialize(deserializer)?;
+            decode(s).map_err(serde::de::Error::custom)
+        }
+    }
+}
+
+/// For `repeated bytes` fields, a JSON array of URL-safe base64 strings.
+///
+/// Decoding errors name the index of the malformed element.
+pub mod urlsafe_base64_vec {
+    use serde::{de, Deserializer, Serializer};
+    use serde_with::{DeserializeAs, SerializeAs};
+    use std::fmt::Formatter;
+
+    use super::urlsafe_base64;
+
+    pub struct Wrapper;
+
+    impl SerializeAs<Vec<Vec<u8>>> for Wrapper {
+        fn serialize_as<S>(value: &Vec<Vec<u8>>, s: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            s.collect_seq(value.iter().map(urlsafe_base64::to_string))
+        }
+    }
+
+    struct Visitor;
+
+    impl<'de> de::Visitor<'de> for Visitor {
+        type Value = Vec<Vec<u8>>;
+
+        fn expecting(&self, f: &mut Formatter) -> std::fmt::Result {
+            write!(f, "a sequence of base64 strings")
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: de::SeqAccess<'de>,
+        {
+            let mut values = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+            // owned, as borrowing fails for escaped strings and for deserializers like `Value`
+            while let Some(s) = seq.next_element::<String>()? {
+                let value = urlsafe_base64::decode(&s).map_err(|err| {
+                    de::Error::custom(format!("element {}: {}", values.len(), err))
+                })?;
+                values.push(value);
+            }
+            Ok(values)
+        }
+    }
+
+    impl<'de> DeserializeAs<'de, Vec<Vec<u8>>> for Wrapper {
+        fn deserialize_as<D>(deserializer: D) -> Result<Vec<Vec<u8>>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_seq(Visitor)
+        }
+    }
+}
+
+/// For `map<string, bytes>` fields, a JSON object with URL-safe base64 string values.
+///
+/// Decoding errors name the key of the malformed value.
+pub mod urlsafe_base64_map {
+    use serde::{de, Deserializer, Serializer};
+    use serde_with::{DeserializeAs, SerializeAs};
+    use std::collections::HashMap;
+    use std::fmt::Formatter;
+
+    use super::urlsafe_base64;
+
+    pub struct Wrapper;
+
+    impl SerializeAs<HashMap<String, Vec<u8>>> for Wrapper {
+        fn serialize_as<S>(value: &HashMap<String, Vec<u8>>, s: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            s.collect_map(
+                value
+                    .iter()
+                    .map(|(key, bytes)| (key, urlsafe_base64::to_string(bytes))),
+            )
+        }
+    }
+
+    struct Visitor;
+
+    impl<'de> de::Visitor<'de> for Visitor {
+        type Value = HashMap<String, Vec<u8>>;
+
+        fn expecting(&self, f: &mut Formatter) -> std::fmt::Result {
+            write!(f, "a map of base64 strings")
+        }
+
+        fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+        where
+            A: de::MapAccess<'de>,
+        {
+            let mut values = HashMap::with_capacity(map.size_hint().unwrap_or(0));
+            while let Some((key, s)) = map.next_entry::<String, String>()? {
+                let value = urlsafe_base64::decode(&s)
+                    .map_err(|err| de::Error::custom(format!("key '{}': {}", key, err)))?;
+                values.insert(key, value);
+            }
+            Ok(values)
+        }
+    }
+
+    impl<'de> DeserializeAs<'de, HashMap<String, Vec<u8>>> for Wrapper {
+        fn deserialize_as<D>(deserializer: D) -> Result<HashMap<String, Vec<u8>>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_map(Visitor)
+        }
+    }
+}
+
+/// Like [`urlsafe_base64`], but using the standard base64 alphabet with `+` and `/`, as the proto3
+/// JSON mapping specifies for `bytes`.
+pub mod standard_base64 {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use serde_with::{DeserializeAs, SerializeAs};
+
+    pub struct Wrapper;
+
+    pub fn to_string(bytes: &Vec<u8>) -> String {
+        base64::encode_config(bytes, base64::STANDARD)
+    }
+
+    impl SerializeAs<Vec<u8>> for Wrapper {
+        fn serialize_as<S>(value: &Vec<u8>, s: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            s.serialize_str(&to_string(value))
+        }
+    }
+
+    impl<'de> DeserializeAs<'de, Vec<u8>> for Wrapper {
+        fn deserialize_as<D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let s: &str = Deserialize::deserialize(deserializer)?;
+            base64::decode_config(s, base64::STANDARD).map_err(serde::de::Error::custom)
+        }
+    }
+}
+
+/// Like `serde_with::DisplayFromStr`, values are serialized as strings using [`Display`], but
+/// JSON numbers are accepted on deserialization as well, as the proto3 JSON mapping permits for
+/// 64-bit integers.
+///
+/// Strings are parsed with the target's [`FromStr`], so float targets also accept scientific
+/// notation like `"3.14e2"`, which integer targets reject.
+///
+/// [`Display`]: std::fmt::Display
+/// [`FromStr`]: std::str::FromStr
+pub mod str_like {
+    use serde::{de, Deserializer, Serializer};
+    use serde_with::{DeserializeAs, SerializeAs};
+    use std::fmt::{Display, Formatter};
+    use std::marker::PhantomData;
+    use std::str::FromStr;
+
+    pub struct Wrapper;
+
+    impl<T: Display> SerializeAs<T> for Wrapper {
+        fn serialize_as<S>(value: &T, s: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            s.collect_str(value)
+        }
+    }
+
+    pub(super) struct StrLikeVisitor<T> {
+        /// Whether surrounding ASCII whitespace is trimmed from strings before parsing.
+        pub(super) trim: bool,
+        pub(super) marker: PhantomData<T>,
+    }
+
+    impl<T> StrLikeVisitor<T>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        fn parse<E: de::Error>(v: &str) -> Result<T, E> {
+            T::from_str(v).map_err(de::Error::custom)
+        }
+    }
+
+    impl<'de, T> de::Visitor<'de> for StrLikeVisitor<T>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        type Value = T;
+
+        fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+            formatter.write_str("a string or a number")
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<T, E> {
+            if self.trim {
+                Self::parse(v.trim_matches(|c: char| c.is_ascii_whitespace()))
+            } else {
+                Self::parse(v)
+            }
+        }
+
+        fn visit_i64<E: de::Error>(self, v: i64) -> Result<T, E> {
+            Self::parse(&v.to_string())
+        }
+
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<T, E> {
+            Self::parse(&v.to_string())
+        }
+
+        fn visit_f64<E: de::Error>(self, v: f64) -> Result<T, E> {
+            Self::parse(&v.to_string())
+        }
+    }
+
+    impl<'de, T> DeserializeAs<'de, T> for Wrapper
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        fn deserialize_as<D>(deserializer: D) -> Result<T, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_any(StrLikeVisitor {
+                trim: false,
+                marker: PhantomData,
+            })
+        }
+    }
+}
+
+/// Like [`str_like`], but trims surrounding ASCII whitespace from strings before parsing them,
+/// as some producers send numeric strings like `" 42 "`.
+///
+/// Serialization is unchanged.
+pub mod str_like_trim {
+    use serde::{Deserializer, Serializer};
+    use serde_with::{DeserializeAs, SerializeAs};
+    use std::fmt::Display;
+    use std::marker::PhantomData;
+    use std::str::FromStr;
+
+    use super::str_like::{self, StrLikeVisitor};
+
+    pub struct Wrapper;
+
+    impl<T: Display> SerializeAs<T> for Wrapper {
+        fn serialize_as<S>(value: &T, s: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            str_like::Wrapper::serialize_as(value, s)
+        }
+    }
+
+    impl<'de, T> DeserializeAs<'de, T> for Wrapper
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        fn deserialize_as<D>(deserializer: D) -> Result<T, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_any(StrLikeVisitor {
+                trim: true,
+                marker: PhantomData,
+            })
+        }
+    }
+}
+
+/// Like [`str_like`], but for optional fields which some APIs send as `""` when unset, which is
+/// deserialized as `None`, like `null`.
+///
+/// `None` is serialized as `null`.
+pub mod str_like_empty_as_none {
+    use serde::de;
+    use serde::{Deserializer, Serializer};
+    use serde_with::{DeserializeAs, SerializeAs};
+    use std::fmt::{Display, Formatter};
+    use std::marker::PhantomData;
+    use std::str::FromStr;
+
+    use super::str_like::{self, StrLikeVisitor};
+
+    pub struct Wrapper;
+
+    impl<T: Display> SerializeAs<Option<T>> for Wrapper {
+        fn serialize_as<S>(value: &Option<T>, s: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            match value {
+                Some(value) => str_like::Wrapper::serialize_as(value, s),
+                None => s.serialize_none(),
+            }
+        }
+    }
+
+    struct Visitor<T>(PhantomData<T>);
+
+    impl<T> Visitor<T> {
+        fn inner(&self) -> StrLikeVisitor<T> {
+            StrLikeVisitor {
+                trim: false,
+                marker: PhantomData,
+            }
+        }
+    }
+
+    impl<'de, T> de::Visitor<'de> for Visitor<T>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        type Value = Option<T>;
+
+        fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+            formatter.write_str("a string, a number or null")
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Option<T>, E> {
+            if v.is_empty() {
+                return Ok(None);
+            }
+            self.inner().visit_str(v).map(Some)
+        }
+
+        fn visit_i64<E: de::Error>(self, v: i64) -> Result<Option<T>, E> {
+            self.inner().visit_i64(v).map(Some)
+        }
+
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<Option<T>, E> {
+            self.inner().visit_u64(v).map(Some)
+        }
+
+        fn visit_f64<E: de::Error>(self, v: f64) -> Result<Option<T>, E> {
+            self.inner().visit_f64(v).map(Some)
+        }
+
+        fn visit_unit<E: de::Error>(self) -> Result<Option<T>, E> {
+            Ok(None)
+        }
+    }
+
+    impl<'de, T> DeserializeAs<'de, Option<T>> for Wrapper
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        fn deserialize_as<D>(deserializer: D) -> Result<Option<T>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_any(Visitor(PhantomData))
+        }
+    }
+}
+
+/// Accepts a 32-bit integer given as a JSON number or a numeric string, as the proto3 JSON
+/// mapping permits for `int32` and `uint32` values.
+struct Int32Visitor<T>(std::marker::PhantomData<T>);
+
+impl<'de, T> serde::de::Visitor<'de> for Int32Visitor<T>
+where
+    T: TryFrom<i64> + TryFrom<u64> + std::str::FromStr,
+    <T as std::str::FromStr>::Err: std::fmt::Display,
+{
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a 32-bit integer as a number or a string")
+    }
+
+    fn visit_i64<E: serde::de::Error>(self, v: i64) -> Result<T, E> {
+        T::try_from(v).map_err(|_| E::invalid_value(serde::de::Unexpected::Signed(v), &self))
+    }
+
+    fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<T, E> {
+        T::try_from(v).map_err(|_| E::invalid_value(serde::de::Unexpected::Unsigned(v), &self))
+    }
+
+    fn visit_f64<E: serde::de::Error>(self, v: f64) -> Result<T, E> {
+        // integral floats like `1.0` or `1e3` are valid encodings of integers
+        if v.fract() == 0.0 && v.abs() <= u32::MAX as f64 {
+            self.visit_i64(v as i64)
+        } else {
+            Err(E::invalid_value(serde::de::Unexpected::Float(v), &self))
+        }
+    }
+
+    fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<T, E> {
+        T::from_str(v).map_err(E::custom)
+    }
+}
+
+/// For `google.protobuf.Int32Value`, whose purpose is to tell an explicit `0` apart from an
+/// absent value: use it as `Option<int32_value::Wrapper>`, which deserializes a present `0` as
+/// `Some(0)` and a missing field or `null` as `None`, and serializes `Some(0)` as `0`.
+pub mod int32_value {
+    use serde::{Deserializer, Serializer};
+    use serde_with::{DeserializeAs, SerializeAs};
+    use std::marker::PhantomData;
+
+    pub struct Wrapper;
+
+    impl SerializeAs<i32> for Wrapper {
+        fn serialize_as<S>(value: &i32, s: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            s.serialize_i32(*value)
+        }
+    }
+
+    impl<'de> DeserializeAs<'de, i32> for Wrapper {
+        fn deserialize_as<D>(deserializer: D) -> Result<i32, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_any(super::Int32Visitor(PhantomData))
+        }
+    }
+}
+
+/// Like [`int32_value`], but for `google.protobuf.UInt32Value`.
+pub mod uint32_value {
+    use serde::{Deserializer, Serializer};
+    use serde_with::{DeserializeAs, SerializeAs};
+    use std::marker::PhantomData;
+
+    pub struct Wrapper;
+
+    impl SerializeAs<u32> for Wrapper {
+        fn serialize_as<S>(value: &u32, s: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            s.serialize_u32(*value)
+        }
+    }
+
+    impl<'de> DeserializeAs<'de, u32> for Wrapper {
+        fn deserialize_as<D>(deserializer: D) -> Result<u32, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_any(super::Int32Visitor(PhantomData))
+        }
+    }
+}
+
+/// Accepts a floating point number given as a JSON number or a string, which may be one of the
+/// special values `"Infinity"`, `"-Infinity"` and `"NaN"` of the proto3 JSON mapping.
+struct FloatVisitor;
+
+impl<'de> serde::de::Visitor<'de> for FloatVisitor {
+    type Value = f64;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a number, or one of \"Infinity\", \"-Infinity\" or \"NaN\"")
+    }
+
+    fn visit_i64<E: serde::de::Error>(self, v: i64) -> Result<f64, E> {
+        Ok(v as f64)
+    }
+
+    fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<f64, E> {
+        Ok(v as f64)
+    }
+
+    fn visit_f64<E: serde::de::Error>(self, v: f64) -> Result<f64, E> {
+        Ok(v)
+    }
+
+    fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<f64, E> {
+        match v {
+            "Infinity" => Ok(f64::INFINITY),
+            "-Infinity" => Ok(f64::NEG_INFINITY),
+            "NaN" => Ok(f64::NAN),
+            // rust would accept the above in various cases, and "inf", which proto3 doesn't
+            _ if v.contains(|c: char| c.is_ascii_alphabetic() && c != 'e' && c != 'E') => {
+                Err(E::invalid_value(serde::de::Unexpected::Str(v), &self))
+            }
+            _ => v.parse().map_err(E::custom),
+        }
+    }
+}
+
+/// Serializes `value` as a JSON number, or as one of the strings `"Infinity"`, `"-Infinity"` and
+/// `"NaN"` if it isn't finite.
+fn serialize_float<S: serde::Serializer>(value: f64, s: S) -> Result<S::Ok, S::Error> {
+    if value.is_nan() {
+        s.serialize_str("NaN")
+    } else if value == f64::INFINITY {
+        s.serialize_str("Infinity")
+    } else if value == f64::NEG_INFINITY {
+        s.serialize_str("-Infinity")
+    } else {
+        s.serialize_f64(value)
+    }
+}
+
+/// For `double` values and `google.protobuf.DoubleValue`, which represent non-finite numbers as
+/// the strings `"Infinity"`, `"-Infinity"` and `"NaN"`.
+pub mod double_value {
+    use serde::{Deserializer, Serializer};
+    use serde_with::{DeserializeAs, SerializeAs};
+
+    pub struct Wrapper;
+
+    impl SerializeAs<f64> for Wrapper {
+        fn serialize_as<S>(value: &f64, s: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            super::serialize_float(*value, s)
+        }
+    }
+
+    impl<'de> DeserializeAs<'de, f64> for Wrapper {
+        fn deserialize_as<D>(deserializer: D) -> Result<f64, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_any(super::FloatVisitor)
+        }
+    }
+}
+
+/// Like [`double_value`], but for `float` values and `google.protobuf.FloatValue`.
+pub mod float_value {
+    use serde::{de, Deserializer, Serializer};
+    use serde_with::{DeserializeAs, SerializeAs};
+
+    pub struct Wrapper;
+
+    impl SerializeAs<f32> for Wrapper {
+        fn serialize_as<S>(value: &f32, s: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            if value.is_finite() {
+                // widening to f64 would print 0.1 as 0.10000000149011612
+                s.serialize_f32(*value)
+            } else {
+                super::serialize_float(f64::from(*value), s)
+            }
+        }
+    }
+
+    impl<'de> DeserializeAs<'de, f32> for Wrapper {
+        fn deserialize_as<D>(deserializer: D) -> Result<f32, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let value = deserializer.deserialize_any(super::FloatVisitor)?;
+            if value.is_finite() && (value as f32).is_infinite() {
+                return Err(de::Error::custom(format!(
+                    "{} is out of range for a float",
+                    value
+                )));
+            }
+            Ok(value as f32)
+        }
+    }
+}
+
+/// For `repeated` enum fields which are used as a set of flags, serialized as a JSON array of the
+/// variant names.
+///
+/// Works with `Vec<T>` and `HashSet<T>`, where duplicates are removed on deserialization, and a
+/// `Vec` keeps the order in which variants were first seen. Each element is (de)serialized by the
+/// `Serialize` and `Deserialize` implementations of `T`, so enums defined in [`crate::types`]
+/// accept variant numbers as well. Whether unknown names are kept or rejected is up to `T`.
+pub mod enum_set {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use serde_with::{DeserializeAs, SerializeAs};
+    use std::collections::HashSet;
+    use std::hash::Hash;
+
+    pub struct Wrapper;
+
+    impl<T: Serialize> SerializeAs<Vec<T>> for Wrapper {
+        fn serialize_as<S>(value: &Vec<T>, s: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            s.collect_seq(value)
+        }
+    }
+
+    impl<'de, T> DeserializeAs<'de, Vec<T>> for Wrapper
+    where
+        T: Deserialize<'de> + PartialEq,
+    {
+        fn deserialize_as<D>(deserializer: D) -> Result<Vec<T>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let values: Vec<T> = Deserialize::deserialize(deserializer)?;
+            let mut unique = Vec::with_capacity(values.len());
+            for value in values {
+                if !unique.contains(&value) {
+                    unique.push(value);
+                }
+            }
+            Ok(unique)
+        }
+    }
+
+    impl<T: Serialize> SerializeAs<HashSet<T>> for Wrapper {
+        fn serialize_as<S>(value: &HashSet<T>, s: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            s.collect_seq(value)
+        }
+    }
+
+    impl<'de, T> DeserializeAs<'de, HashSet<T>> for Wrapper
+    where
+        T: Deserialize<'de> + Eq + Hash,
+    {
+        fn deserialize_as<D>(deserializer: D) -> Result<HashSet<T>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let values: Vec<T> = Deserialize::deserialize(deserializer)?;
+            Ok(values.into_iter().collect())
+        }
+    }
+}
+
+/// For maps keyed by an enum, like a schedule keyed by
+/// [`DayOfWeek`](crate::types::day_of_week::DayOfWeek), which are JSON objects keyed by the
+/// variant names.
+///
+/// Keys are (de)serialized using the [`Display`] and [`FromStr`] implementations of the enum, and
+/// values by their own `Serialize` and `Deserialize` implementations. Parsing errors name the key.
+///
+/// [`Display`]: std::fmt::Display
+/// [`FromStr`]: std::str::FromStr
+pub mod enum_keyed_map {
+    use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+    use serde_with::{DeserializeAs, SerializeAs};
+    use std::collections::HashMap;
+    use std::fmt::{Display, Formatter};
+    use std::hash::Hash;
+    use std::marker::PhantomData;
+    use std::str::FromStr;
+
+    pub struct Wrapper;
+
+    impl<K: Display, V: Serialize> SerializeAs<HashMap<K, V>> for Wrapper {
+        fn serialize_as<S>(value: &HashMap<K, V>, s: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            s.collect_map(value.iter().map(|(key, v)| (key.to_string(), v)))
+        }
+    }
+
+    struct Visitor<K, V>(PhantomData<(K, V)>);
+
+    impl<'de, K, V> de::Visitor<'de> for Visitor<K, V>
+    where
+        K: FromStr + Eq + Hash,
+        K::Err: Display,
+        V: Deserialize<'de>,
+    {
+        type Value = HashMap<K, V>;
+
+        fn expecting(&self, f: &mut Formatter) -> std::fmt::Result {
+            write!(f, "a map keyed by enum names")
+        }
+
+        fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+        where
+            A: de::MapAccess<'de>,
+        {
+            let mut values = HashMap::with_capacity(map.size_hint().unwrap_or(0));
+            while let Some(key) = map.next_key::<String>()? {
+                let parsed = K::from_str(&key).map_err(de::Error::custom)?;
+                let value = map
+                    .next_value::<V>()
+                    .map_err(|err| de::Error::custom(format!("key '{}': {}", key, err)))?;
+                values.insert(parsed, value);
+            }
+            Ok(values)
+        }
+    }
+
+    impl<'de, K, V> DeserializeAs<'de, HashMap<K, V>> for Wrapper
+    where
+        K: FromStr + Eq + Hash,
+        K::Err: Display,
+        V: Deserialize<'de>,
+    {
+        fn deserialize_as<D>(deserializer: D) -> Result<HashMap<K, V>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_map(Visitor(PhantomData))
+        }
+    }
+}
+
+/// For `repeated` fields which are serialized as `null` rather than `[]` when empty, and
+/// deserialized as an empty `Vec` from `null`.
+///
+/// To omit an empty field entirely, and accept it missing, combine this with
+/// `#[serde(default, skip_serializing_if = "Vec::is_empty")]`.
+pub mod skip_empty_vec {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use serde_with::{DeserializeAs, SerializeAs};
+
+    pub struct Wrapper;
+
+    impl<T: Serialize> SerializeAs<Vec<T>> for Wrapper {
+        fn serialize_as<S>(value: &Vec<T>, s: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            if value.is_empty() {
+                s.serialize_none()
+            } else {
+                s.collect_seq(value)
+            }
+        }
+    }
+
+    impl<'de, T: Deserialize<'de>> DeserializeAs<'de, Vec<T>> for Wrapper {
+        fn deserialize_as<D>(deserializer: D) -> Result<Vec<T>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let values: Option<Vec<T>> = Deserialize::deserialize(deserializer)?;
+            Ok(values.unwrap_or_default())
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+pub fn datetime_to_string(datetime: &chrono::DateTime<chrono::offset::Utc>) -> String {
+    datetime.to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
+}
+
+/// Runs `deserialize`, prefixing any error it returns with `field`, e.g. `retry_delay: ...`.
+///
+/// Errors of a `with` module don't mention the field they failed on, which makes them hard to
+/// trace when many fields share a module. This wraps such a module for use in a
+/// `deserialize_with` function or a custom `Deserialize` implementation:
+///
+/// ```
+/// use google_apis_common::serde::{deserialize_with_context, str_like};
+/// use serde::Deserializer;
+/// use serde_with::DeserializeAs;
+///
+/// fn page_size<'de, D: Deserializer<'de>>(deserializer: D) -> Result<i64, D::Error> {
+///     deserialize_with_context(deserializer, "page_size", str_like::Wrapper::deserialize_as)
+/// }
+/// ```
+pub fn deserialize_with_context<'de, D, T, F>(
+    deserializer: D,
+    field: &str,
+    deserialize: F,
+) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    F: FnOnce(D) -> Result<T, D::Error>,
+{
+    deserialize(deserializer).map_err(|err| serde::de::Error::custom(format!("{}: {}", field, err)))
+}
+
+/// Defines an enum for a proto3 `oneof`, which (de)serializes as an object with the set field as
+/// its single key, as part of the message containing the oneof.
+///
+/// Every variant holds the value of one field and names its JSON key. Deserialization fails if
+/// the object contains more than one or none of the keys, and ignores any other keys, so that the
+/// enum can be `#[serde(flatten)]`ed into the struct of the message.
+///
+/// ```
+/// google_apis_common::proto_oneof! {
+///     #[derive(Clone, Debug, PartialEq)]
+///     pub enum Source {
+///         Uri(String) => "uri",
+///         Content(Vec<u8>) => "content",
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! proto_oneof {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $(
+                $(#[$variant_meta:meta])*
+                $variant:ident($ty:ty) => $key:literal,
+            )*
+        }
+    ) => {
+        $(#[$meta])*
+        $vis enum $name {
+            $(
+                $(#[$variant_meta])*
+                $variant($ty),
+            )*
+        }
+
+        impl $crate::__serde::Serialize for $name {
+            fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+            where
+                S: $crate::__serde::Serializer,
+            {
+                use $crate::__serde::ser::SerializeMap;
+
+                let mut map = s.serialize_map(Some(1))?;
+                match self {
+                    $($name::$variant(value) => map.serialize_entry($key, value)?,)*
+                }
+                map.end()
+            }
+        }
+
+        impl<'de> $crate::__serde::Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: $crate::__serde::Deserializer<'de>,
+            {
+                struct Visitor;
+
+                impl<'de> $crate::__serde::de::Visitor<'de> for Visitor {
+                    type Value = $name;
+
+                    fn expecting(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                        write!(f, "an object with one of the keys {:?}", [$($key),*])
+                    }
+
+                    fn visit_map<A>(self, mut map: A) -> Result<$name, A::Error>
+                    where
+                        A: $crate::__serde::de::MapAccess<'de>,
+                    {
+                        let mut value: Option<$name> = None;
+                        while let Some(key) = map.next_key::<String>()? {
+                            let variant = match key.as_str() {
+                                $($key => $name::$variant(map.next_value()?),)*
+                                _ => {
+                                    map.next_value::<$crate::__serde::de::IgnoredAny>()?;
+                                    continue;
+                                }
+                            };
+                            if value.is_some() {
+                                return Err($crate::__serde::de::Error::custom(format!(
+                                    "only one of {:?} may be set",
+                                    [$($key),*]
+                                )));
+                            }
+                            value = Some(variant);
+                        }
+                        value.ok_or_else(|| {
+                            $crate::__serde::de::Error::custom(format!(
+                                "one of {:?} must be set",
+                                [$($key),*]
+                            ))
+                        })
+                    }
+                }
+
+                deserializer.deserialize_map(Visitor)
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        double_value, enum_keyed_map, enum_set, field_mask_array, float_value, int32_value,
+        list_value, skip_empty_vec, str_like, str_like_empty_as_none, str_like_map, str_like_trim,
+        uint32_value, urlsafe_base64, urlsafe_base64_map, urlsafe_base64_vec,
+    };
+    #[cfg(feature = "chrono")]
+    use super::{
+        duration, duration_clamped, duration_compat, duration_lenient, duration_nanos,
+        duration_padded, duration_struct, timestamp, timestamp_lenient, timestamp_unix,
+    };
+    use crate::types::day_of_week::DayOfWeek;
+    #[cfg(feature = "chrono")]
+    use proptest::prelude::*;
+    use serde::{Deserialize, Serialize};
+    use serde_with::{serde_as, DisplayFromStr};
+    use std::collections::HashSet;
+
+    #[cfg(feature = "chrono")]
+    #[serde_as]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct DurationWrapper {
+        #[serde_as(as = "Option<duration::Wrapper>")]
+        duration: Option<chrono::Duration>,
+    }
+
+    #[cfg(feature = "chrono")]
+    #[serde_as]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct ClampedDurationWrapper {
+        #[serde_as(as = "Option<duration_clamped::Wrapper>")]
+        duration: Option<chrono::Duration>,
+    }
+
+    #[serde_as]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Base64Wrapper {
+        #[serde_as(as = "Option<urlsafe_base64::Wrapper>")]
+        bytes: Option<Vec<u8>>,
+    }
+
+    #[serde_as]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct I64Wrapper {
+        #[serde_as(as = "Option<DisplayFromStr>")]
+        num: Option<i64>,
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_duration_de_success_cases() {
+        let durations = [
+            ("-0.2s", -200_000_000),
+            ("0.000000001s", 1),
+            ("999.999999999s", 999_999_999_999),
+            ("129s", 129_000_000_000),
+            ("3600s", 3_600_000_000_000),
+            ("-3600s", -3_600_000_000_000),
+            ("0s", 0),
+            ("0.123456789s", 123_456_789),
+            ("1.000000000s", 1_000_000_000),
+            ("0.000000000s", 0),
+            ("-0.000000000s", 0),
+            ("-1.000000000s", -1_000_000_000),
+            ("1.1000000000s", 1_100_000_000),
+            ("1.100000000000s", 1_100_000_000),
+            ("1.1111111110000s", 1_111_111_111),
+            ("-0.0000000010000s", -1),
+            ("-0.5s", -500_000_000),
+            ("-0.05s", -50_000_000),
+            ("-0.005s", -5_000_000),
+            ("-0.0005s", -500_000),
+            ("-0.00005s", -50_000),
+            ("-0.000005s", -5_000),
+            ("-0.0000005s", -500),
+            ("-0.00000005s", -50),
+            ("-0.000000001s", -1),
+            ("-0.123456789s", -123_456_789),
+        ];
+        for (repr, nanos) in durations.into_iter() {
+            let wrapper: DurationWrapper =
+                serde_json::from_str(&format!("{{\"duration\": \"{}\"}}", repr)).unwrap();
+            assert_eq!(
+                Some(nanos),
+                wrapper.duration.unwrap().num_nanoseconds(),
+                "parsed \"{}\" expecting Duration with {}ns",
+                repr,
+                nanos
+            );
+        }
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_duration_de_failure_cases() {
+        let durations = ["1.-3s", "1.1111111111s", "1.11111111110s", "1.2"];
+        for repr in durations.into_iter() {
+            assert!(
+                serde_json::from_str::<DurationWrapper>(&format!("{{\"duration\": \"{}\"}}", repr))
+                    .is_err(),
+                "parsed \"{}\" expecting err",
+                repr
+            );
+        }
+        let err = serde_json::from_str::<DurationWrapper>(r#"{"duration": "1.1234567891s"}"#)
+            .unwrap_err();
+        assert!(err.to_string().contains("'1.1234567891s'"), "{}", err);
+        for repr in ["s", "-s"] {
+            assert!(matches!(
+                duration::duration_from_str(repr),
+                Err(duration::ParseDurationError::EmptyValue)
+            ));
+            let err =
+                serde_json::from_str::<DurationWrapper>(&format!(r#"{{"duration": "{}"}}"#, repr))
+                    .unwrap_err();
+            assert!(err.to_string().contains("no number before"), "{}", err);
+        }
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_duration_de_range_boundaries() {
+        // the range of google.protobuf.Duration includes its bounds
+        for seconds in [duration::MAX_SECONDS, duration::MIN_SECONDS] {
+            assert_eq!(
+                duration::duration_from_str(&format!("{}s", seconds)).unwrap(),
+                chrono::Duration::seconds(seconds)
+            );
+        }
+        assert!(matches!(
+            duration::duration_from_str("315576000001s"),
+            Err(duration::ParseDurationError::SecondOverflow { .. })
+        ));
+        assert!(matches!(
+            duration::duration_from_str("-315576000001s"),
+            Err(duration::ParseDurationError::SecondUnderflow { .. })
+        ));
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_duration_ser_success_cases() {
+        let durations = [
+            -200_000_000,
+            1,
+            999_999_999_999,
+            129_000_000_000,
+            123_456_789,
+        ];
+
+        for nanos in durations.into_iter() {
+            let wrapper = DurationWrapper {
+                duration: Some(chrono::Duration::nanoseconds(nanos)),
+            };
+            let s = serde_json::to_string(&wrapper);
+            assert!(s.is_ok(), "Could not serialize {}ns", nanos);
+            let s = s.unwrap();
+            assert_eq!(
+                wrapper,
+                serde_json::from_str(&s).unwrap(),
+                "round trip should return same duration"
+            );
+        }
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_duration_write() {
+        let mut buf = String::new();
+        for (duration, expected) in [
+            (chrono::Duration::milliseconds(1500), "1.500s"),
+            (chrono::Duration::microseconds(1_000_001), "1.000001s"),
+            (chrono::Duration::nanoseconds(1_000_000_010), "1.000000010s"),
+            (chrono::Duration::seconds(-3600), "-3600s"),
+            (chrono::Duration::microseconds(-500), "-0.000500s"),
+            (chrono::Duration::nanoseconds(-1), "-0.000000001s"),
+        ] {
+            buf.clear();
+            duration::write_duration(&duration, &mut buf).unwrap();
+            assert_eq!(buf, expected);
+            assert_eq!(buf, duration::to_string(&duration));
+        }
+
+        let mut path = String::from("v1/leases:renew?ttl=");
+        duration::write_duration(&chrono::Duration::seconds(30), &mut path).unwrap();
+        assert_eq!(path, "v1/leases:renew?ttl=30s");
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_duration_fraction_roundtrip() {
+        // a prime step hits fractions of every length, along with the boundaries
+        let fractions = (0..1_000_000_000).step_by(999_983).chain([
+            1,
+            10,
+            100_000_000,
+            500_000_000,
+            999_999_999,
+        ]);
+        for n in fractions {
+            for nanos in [n, -n, 1_000_000_000 + n, -1_000_000_000 - n] {
+                let s = duration::to_string(&chrono::Duration::nanoseconds(nanos));
+                assert_eq!(
+                    duration::duration_from_str(&s).unwrap().num_nanoseconds(),
+                    Some(nanos),
+                    "{}",
+                    s
+                );
+            }
+        }
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_duration_ser_negative_zero() {
+        for zero in [
+            chrono::Duration::seconds(-5) - chrono::Duration::seconds(-5),
+            chrono::Duration::seconds(-5) + chrono::Duration::seconds(5),
+            -chrono::Duration::zero(),
+            chrono::Duration::nanoseconds(-1_500_000_000) + chrono::Duration::milliseconds(1500),
+        ] {
+            assert_eq!(duration::to_string(&zero), "0s");
+            let wrapper = DurationWrapper {
+                duration: Some(zero),
+            };
+            assert_eq!(
+                serde_json::to_string(&wrapper).unwrap(),
+                r#"{"duration":"0s"}"#
+            );
+        }
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_duration_none_is_null_unless_skipped() {
+        let none = DurationWrapper { duration: None };
+        let json = serde_json::to_string(&none).unwrap();
+        assert_eq!(json, r#"{"duration":null}"#);
+        assert_eq!(none, serde_json::from_str(&json).unwrap());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_duration_zero_is_not_none() {
+        #[serde_as]
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        struct OptionalDurationWrapper {
+            #[serde_as(as = "Option<duration::Wrapper>")]
+            #[serde(default, skip_serializing_if = "Option::is_none")]
+            delay: Option<chrono::Duration>,
+        }
+
+        let zero = OptionalDurationWrapper {
+            delay: Some(chrono::Duration::zero()),
+        };
+        let json = serde_json::to_string(&zero).unwrap();
+        assert_eq!(json, r#"{"delay":"0s"}"#);
+        assert_eq!(zero, serde_json::from_str(&json).unwrap());
+
+        let none = OptionalDurationWrapper { delay: None };
+        let json = serde_json::to_string(&none).unwrap();
+        assert_eq!(json, "{}");
+        assert_eq!(none, serde_json::from_str(&json).unwrap());
+        assert_eq!(none, serde_json::from_str(r#"{"delay":null}"#).unwrap());
+    }
+
+    /// Produces `(seconds, nanos)` pairs with a matching sign, as `google.protobuf.Duration`
+    /// requires, from independently shrinkable seconds and nanos.
+    #[cfg(feature = "chrono")]
+    fn proto_duration() -> impl Strategy<Value = (i64, i64)> {
+        // small seconds are weighted in, as zero and its neighbours are where the sign of the
+        // fraction matters most
+        let seconds = prop_oneof![-2..=2i64, duration::MIN_SECONDS..=duration::MAX_SECONDS];
+        (seconds, -999_999_999..=999_999_999i64).prop_map(|(seconds, nanos)| {
+            match seconds.signum() {
+                1 => (seconds, nanos.abs()),
+                -1 => (seconds, -nanos.abs()),
+                _ => (seconds, nanos),
+            }
+        })
+    }
+
+    #[cfg(feature = "chrono")]
+    proptest! {
+        #[test]
+        fn test_duration_roundtrip((seconds, nanos) in proto_duration()) {
+            let expected = chrono::Duration::seconds(seconds) + chrono::Duration::nanoseconds(nanos);
+            let wrapper = DurationWrapper {
+                duration: Some(expected),
+            };
+            let s = serde_json::to_string(&wrapper).unwrap();
+            let parsed: DurationWrapper = serde_json::from_str(&s).unwrap();
+            prop_assert_eq!(Some(expected), parsed.duration, "round trip of {}", s);
+        }
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_duration_rounding_precision() {
+        use duration::{DurationSerializeOptions, Precision};
+
+        let cases = [
+            (Precision::Nanoseconds, 1_234_567_891, "1.234567891s"),
+            (Precision::Nanoseconds, -1, "-0.000000001s"),
+            (Precision::Microseconds, 1_234_567_891, "1.234568s"),
+            (Precision::Microseconds, -1_234_567_491, "-1.234567s"),
+            (Precision::Microseconds, 999_999_999, "1s"),
+            (Precision::Milliseconds, 1_234_567_891, "1.235s"),
+            (Precision::Milliseconds, -500_000, "-0.001s"),
+            (Precision::Milliseconds, 499_999, "0s"),
+        ];
+        for (precision, nanos, expected) in cases {
+            let options = DurationSerializeOptions { precision };
+            let duration = chrono::Duration::nanoseconds(nanos);
+            assert_eq!(
+                options.to_string(&duration),
+                expected,
+                "{}ns rounded to {:?}",
+                nanos,
+                precision
+            );
+            assert_eq!(options.rounded(&duration).to_string(), expected);
+            assert_eq!(
+                serde_json::to_string(&options.rounded(&duration)).unwrap(),
+                format!("\"{}\"", expected)
+            );
+        }
+        assert_eq!(
+            DurationSerializeOptions::default().precision,
+            Precision::Nanoseconds
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_duration_rounded_wrappers() {
+        #[serde_as]
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        struct RoundedWrapper {
+            #[serde_as(as = "duration::MillisecondWrapper")]
+            millis: chrono::Duration,
+            #[serde_as(as = "duration::MicrosecondWrapper")]
+            micros: chrono::Duration,
+        }
+
+        let third = chrono::Duration::seconds(1) / 3;
+        let wrapper = RoundedWrapper {
+            millis: third,
+            micros: third,
+        };
+        let json = serde_json::to_string(&wrapper).unwrap();
+        assert_eq!(json, r#"{"millis":"0.333s","micros":"0.333333s"}"#);
+        let parsed: RoundedWrapper = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.millis, chrono::Duration::milliseconds(333));
+        assert_eq!(parsed.micros, chrono::Duration::microseconds(333_333));
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_duration_checked_arithmetic() {
+        let near_max = chrono::Duration::seconds(duration::MAX_SECONDS - 1);
+        let second = chrono::Duration::seconds(1);
+        assert_eq!(
+            duration::checked_add(&near_max, &second),
+            Some(chrono::Duration::seconds(duration::MAX_SECONDS))
+        );
+        assert_eq!(duration::checked_add(&near_max, &near_max), None);
+        assert_eq!(duration::checked_sub(&-near_max, &near_max), None);
+        assert_eq!(
+            duration::checked_sub(&-near_max, &second),
+            Some(chrono::Duration::seconds(duration::MIN_SECONDS))
+        );
+        assert_eq!(
+            duration::checked_sub(&second, &near_max),
+            Some(chrono::Duration::seconds(2 - duration::MAX_SECONDS))
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_duration_clamped_de() {
+        let durations = [
+            ("315576000001s", 315_576_000_000),
+            ("-315576000000.5s", -315_576_000_000),
+            ("99999999999999999999s", 315_576_000_000),
+            ("-99999999999999999999s", -315_576_000_000),
+            ("315575999999.5s", 315_575_999_999),
+        ];
+        for (repr, seconds) in durations.into_iter() {
+            let wrapper: ClampedDurationWrapper =
+                serde_json::from_str(&format!("{{\"duration\": \"{}\"}}", repr)).unwrap();
+            assert_eq!(
+                seconds,
+                wrapper.duration.unwrap().num_seconds(),
+                "parsed \"{}\" expecting Duration with {}s",
+                repr,
+                seconds
+            );
+        }
+        assert!(serde_json::from_str::<ClampedDurationWrapper>(r#"{"duration": "1.2"}"#).is_err());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_duration_nanos_roundtrip() {
+        #[serde_as]
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        struct NanosDurationWrapper {
+            #[serde_as(as = "duration_nanos::Wrapper")]
+            duration: chrono::Duration,
+        }
+
+        let wrapper = NanosDurationWrapper {
+            duration: chrono::Duration::milliseconds(1500),
+        };
+        let json = serde_json::to_string(&wrapper).unwrap();
+        assert_eq!(json, r#"{"duration":"1500000000"}"#);
+        assert_eq!(wrapper, serde_json::from_str(&json).unwrap());
+        assert_eq!(
+            wrapper,
+            serde_json::from_str(r#"{"duration": 1500000000}"#).unwrap()
+        );
+
+        assert!(serde_json::from_str::<NanosDurationWrapper>(
+            r#"{"duration": "9223372036854775808"}"#
+        )
+        .is_err());
+        let overflow = NanosDurationWrapper {
+            duration: chrono::Duration::seconds(i64::MAX / 1_000_000_000 + 1),
+        };
+        assert!(serde_json::to_string(&overflow).is_err());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_duration_struct_roundtrip() {
+        #[serde_as]
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        struct StructDurationWrapper {
+            #[serde_as(as = "duration_struct::Wrapper")]
+            duration: chrono::Duration,
+        }
+
+        for (nanos, json) in [
+            (
+                123_000_000_456,
+                r#"{"duration":{"seconds":"123","nanos":456}}"#,
+            ),
+            (
+                -1_500_000_000,
+                r#"{"duration":{"seconds":"-1","nanos":-500000000}}"#,
+            ),
+            (-1, r#"{"duration":{"seconds":"0","nanos":-1}}"#),
+            (0, r#"{"duration":{"seconds":"0","nanos":0}}"#),
+        ] {
+            let wrapper = StructDurationWrapper {
+                duration: chrono::Duration::nanoseconds(nanos),
+            };
+            assert_eq!(serde_json::to_string(&wrapper).unwrap(), json);
+            assert_eq!(wrapper, serde_json::from_str(json).unwrap());
+        }
+        assert_eq!(
+            chrono::Duration::seconds(5),
+            serde_json::from_str::<StructDurationWrapper>(r#"{"duration":{"seconds":5}}"#)
+                .unwrap()
+                .duration
+        );
+
+        for json in [
+            r#"{"duration":{"seconds":"1","nanos":-1}}"#,
+            r#"{"duration":{"seconds":"-1","nanos":1}}"#,
+            r#"{"duration":{"seconds":"0","nanos":1000000000}}"#,
+            r#"{"duration":{"seconds":"315576000001"}}"#,
+        ] {
+            assert!(
+                serde_json::from_str::<StructDurationWrapper>(json).is_err(),
+                "parsed {}",
+                json
+            );
+        }
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_duration_padded_ser() {
+        #[serde_as]
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        struct PaddedDurationWrapper {
+            #[serde_as(as = "duration_padded::Wrapper")]
+            duration: chrono::Duration,
+        }
+
+        for (nanos, expected) in [
+            (1_500_000_000, "1.500000000s"),
+            (-1_000, "-0.000001000s"),
+            (2_000_000_000, "2s"),
+        ] {
+            let wrapper = PaddedDurationWrapper {
+                duration: chrono::Duration::nanoseconds(nanos),
+            };
+            let json = serde_json::to_string(&wrapper).unwrap();
+            assert_eq!(json, format!(r#"{{"duration":"{}"}}"#, expected));
+            assert_eq!(wrapper, serde_json::from_str(&json).unwrap());
+        }
+        assert_eq!(
+            duration::to_string(&chrono::Duration::milliseconds(1500)),
+            "1.500s"
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_duration_compat_de() {
+        #[serde_as]
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        struct CompatDurationWrapper {
+            #[serde_as(as = "duration_compat::Wrapper")]
+            duration: chrono::Duration,
+        }
+
+        for (json, duration) in [
+            (r#"{"duration": 129}"#, chrono::Duration::seconds(129)),
+            (r#"{"duration": -5}"#, chrono::Duration::seconds(-5)),
+            (r#"{"duration": "129s"}"#, chrono::Duration::seconds(129)),
+            (
+                r#"{"duration": "1.5s"}"#,
+                chrono::Duration::milliseconds(1500),
+            ),
+        ] {
+            let wrapper: CompatDurationWrapper = serde_json::from_str(json).unwrap();
+            assert_eq!(wrapper.duration, duration, "parsed {}", json);
+        }
+        let wrapper = CompatDurationWrapper {
+            duration: chrono::Duration::seconds(129),
+        };
+        assert_eq!(
+            serde_json::to_string(&wrapper).unwrap(),
+            r#"{"duration":"129s"}"#
+        );
+        for json in [
+            r#"{"duration": "129"}"#,
+            r#"{"duration": 1.5}"#,
+            r#"{"duration": 315576000001}"#,
+        ] {
+            assert!(
+                serde_json::from_str::<CompatDurationWrapper>(json).is_err(),
+                "parsed {}",
+                json
+            );
+        }
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_duration_lenient_de() {
+        #[serde_as]
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        struct LenientDurationWrapper {
+            #[serde_as(as = "duration_lenient::Wrapper")]
+            duration: chrono::Duration,
+        }
+
+        for (repr, seconds) in [("0", 0), ("5", 5), ("5s", 5), ("-5", -5), ("0s", 0)] {
+            let wrapper: LenientDurationWrapper =
+                serde_json::from_str(&format!("{{\"duration\": \"{}\"}}", repr)).unwrap();
+            assert_eq!(
+                wrapper.duration,
+                chrono::Duration::seconds(seconds),
+                "parsed \"{}\"",
+                repr
+            );
+        }
+        let wrapper = LenientDurationWrapper {
+            duration: chrono::Duration::seconds(5),
+        };
+        assert_eq!(
+            serde_json::to_string(&wrapper).unwrap(),
+            r#"{"duration":"5s"}"#
+        );
+        assert!(serde_json::from_str::<LenientDurationWrapper>(r#"{"duration": "1.5"}"#).is_err());
+        assert!(serde_json::from_str::<DurationWrapper>(r#"{"duration": "0"}"#).is_err());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_duration_clamped_ser() {
+        let wrapper = ClampedDurationWrapper {
+            duration: Some(chrono::Duration::seconds(315_576_000_001)),
+        };
+        assert_eq!(
+            r#"{"duration":"315576000000s"}"#,
+            serde_json::to_string(&wrapper).unwrap()
+        );
+        let wrapper = ClampedDurationWrapper {
+            duration: Some(
+                -chrono::Duration::seconds(315_576_000_000) - chrono::Duration::nanoseconds(1),
+            ),
+        };
+        assert_eq!(
+            r#"{"duration":"-315576000000s"}"#,
+            serde_json::to_string(&wrapper).unwrap()
+        );
+    }
+
+    #[test]
+    fn urlsafe_base64_de_success_cases() {
+        let wrapper: Base64Wrapper =
+            serde_json::from_str(r#"{"bytes": "aGVsbG8gd29ybGQ="}"#).unwrap();
+        assert_eq!(Some(b"hello world".as_slice()), wrapper.bytes.as_deref());
+    }
+
+    #[test]
+    fn urlsafe_base64_de_failure_cases() {
+        assert!(serde_json::from_str::<Base64Wrapper>(r#"{"bytes": "aGVsbG8gd29ybG+Q"}"#).is_err());
+    }
+
+    #[test]
+    fn urlsafe_base64_empty_is_not_none() {
+        let empty: Base64Wrapper = serde_json::from_str(r#"{"bytes": ""}"#).unwrap();
+        assert_eq!(empty.bytes, Some(Vec::new()));
+        assert_eq!(serde_json::to_string(&empty).unwrap(), r#"{"bytes":""}"#);
+
+        let null: Base64Wrapper = serde_json::from_str(r#"{"bytes": null}"#).unwrap();
+        assert_eq!(null.bytes, None);
+        assert_eq!(serde_json::to_string(&null).unwrap(), r#"{"bytes":null}"#);
+        assert_eq!(null, serde_json::from_str("{}").unwrap());
+    }
+
+    #[test]
+    fn urlsafe_base64_hints_at_standard_alphabet() {
+        let err =
+            serde_json::from_str::<Base64Wrapper>(r#"{"bytes": "aGVsbG8gd29ybG+Q"}"#).unwrap_err();
+        assert!(err.to_string().contains("standard_base64"), "{}", err);
+
+        let err = serde_json::from_str::<Base64Wrapper>(r#"{"bytes": "a$b"}"#).unwrap_err();
+        assert!(!err.to_string().contains("standard_base64"), "{}", err);
+    }
+
+    #[test]
+    fn urlsafe_base64_roundtrip() {
+        let wrapper = Base64Wrapper {
+            bytes: Some(b"Hello world!".to_vec()),
+        };
+        let s = serde_json::to_string(&wrapper).expect("serialization of bytes infallible");
+        assert_eq!(wrapper, serde_json::from_str::<Base64Wrapper>(&s).unwrap());
+    }
+
+    #[test]
+    fn num_roundtrip() {
+        let wrapper = I64Wrapper {
+            num: Some(i64::MAX),
+        };
+
+        let json_repr = &serde_json::to_string(&wrapper);
+        assert!(json_repr.is_ok(), "serialization should succeed");
+        assert_eq!(
+            wrapper,
+            serde_json::from_str(&format!("{{\"num\": \"{}\"}}", i64::MAX)).unwrap()
+        );
+        assert_eq!(
+            wrapper,
+            serde_json::from_str(json_repr.as_ref().unwrap()).unwrap(),
+            "round trip should succeed"
+        );
+    }
+
+    #[serde_as]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct StrLikeWrapper {
+        #[serde_as(as = "Option<str_like::Wrapper>")]
+        num: Option<i64>,
+    }
+
+    #[test]
+    fn str_like_roundtrip() {
+        let wrapper = StrLikeWrapper {
+            num: Some(i64::MIN),
+        };
+        let s = serde_json::to_string(&wrapper).unwrap();
+        assert_eq!(s, r#"{"num":"-9223372036854775808"}"#);
+        assert_eq!(wrapper, serde_json::from_str(&s).unwrap());
+        assert_eq!(
+            StrLikeWrapper { num: Some(42) },
+            serde_json::from_str(r#"{"num": 42}"#).unwrap()
+        );
+        assert!(serde_json::from_str::<StrLikeWrapper>(r#"{"num": "4.2"}"#).is_err());
+    }
+
+    #[test]
+    fn str_like_empty_as_none() {
+        #[serde_as]
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        struct EmptyAsNoneWrapper {
+            #[serde_as(as = "str_like_empty_as_none::Wrapper")]
+            #[serde(default)]
+            count: Option<i64>,
+        }
+
+        for (json, count) in [
+            (r#"{"count": ""}"#, None),
+            (r#"{"count": null}"#, None),
+            (r#"{}"#, None),
+            (r#"{"count": "7"}"#, Some(7)),
+            (r#"{"count": 7}"#, Some(7)),
+        ] {
+            let wrapper: EmptyAsNoneWrapper = serde_json::from_str(json).unwrap();
+            assert_eq!(wrapper.count, count, "parsed {}", json);
+        }
+        assert_eq!(
+            serde_json::to_string(&EmptyAsNoneWrapper { count: Some(7) }).unwrap(),
+            r#"{"count":"7"}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&EmptyAsNoneWrapper { count: None }).unwrap(),
+            r#"{"count":null}"#
+        );
+        assert!(serde_json::from_str::<EmptyAsNoneWrapper>(r#"{"count": " "}"#).is_err());
+        assert!(serde_json::from_str::<StrLikeWrapper>(r#"{"num": ""}"#).is_err());
+    }
+
+    #[test]
+    fn str_like_trim() {
+        #[serde_as]
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        struct TrimWrapper {
+            #[serde_as(as = "str_like_trim::Wrapper")]
+            num: i64,
+        }
+
+        for json in [
+            r#"{"num": " 42 "}"#,
+            r#"{"num": "\t42\n"}"#,
+            r#"{"num": 42}"#,
+        ] {
+            let wrapper: TrimWrapper = serde_json::from_str(json).unwrap();
+            assert_eq!(wrapper.num, 42, "parsed {}", json);
+        }
+        assert_eq!(
+            serde_json::to_string(&TrimWrapper { num: 42 }).unwrap(),
+            r#"{"num":"42"}"#
+        );
+        assert!(serde_json::from_str::<TrimWrapper>(r#"{"num": "4 2"}"#).is_err());
+        assert!(serde_json::from_str::<StrLikeWrapper>(r#"{"num": " 42 "}"#).is_err());
+    }
+
+    #[test]
+    fn str_like_bool() {
+        #[serde_as]
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        struct BoolStrWrapper {
+            #[serde_as(as = "str_like::Wrapper")]
+            flag: bool,
+        }
+
+        for flag in [true, false] {
+            let wrapper = BoolStrWrapper { flag };
+            let json = serde_json::to_string(&wrapper).unwrap();
+            assert_eq!(json, format!(r#"{{"flag":"{}"}}"#, flag));
+            assert_eq!(wrapper, serde_json::from_str(&json).unwrap());
+        }
+        // `bool::from_str` only accepts lowercase `true` and `false`
+        for json in [r#"{"flag": "TRUE"}"#, r#"{"flag": "1"}"#, r#"{"flag": 1}"#] {
+            assert!(
+                serde_json::from_str::<BoolStrWrapper>(json).is_err(),
+                "parsed {}",
+                json
+            );
+        }
+    }
+
+    #[test]
+    fn str_like_scientific_notation() {
+        #[serde_as]
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        struct FloatWrapper {
+            #[serde_as(as = "str_like::Wrapper")]
+            value: f64,
+        }
+
+        let parsed: FloatWrapper = serde_json::from_str(r#"{"value": "3.14e2"}"#).unwrap();
+        assert_eq!(parsed.value, 314.0);
+        let parsed: FloatWrapper = serde_json::from_str(r#"{"value": 3.14e2}"#).unwrap();
+        assert_eq!(parsed.value, 314.0);
+        assert!(serde_json::from_str::<StrLikeWrapper>(r#"{"num": "3.14e2"}"#).is_err());
+    }
+
+    #[test]
+    fn str_like_map_roundtrip() {
+        #[serde_as]
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        struct StrLikeMapWrapper {
+            #[serde_as(as = "str_like_map::Wrapper")]
+            counts: std::collections::HashMap<String, i64>,
+        }
+
+        let wrapper = StrLikeMapWrapper {
+            counts: [("min".to_string(), i64::MIN), ("one".to_string(), 1)]
+                .into_iter()
+                .collect(),
+        };
+        let json = serde_json::to_value(&wrapper).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"counts": {"min": "-9223372036854775808", "one": "1"}})
+        );
+        assert_eq!(wrapper, serde_json::from_value(json).unwrap());
+
+        let err = serde_json::from_str::<StrLikeMapWrapper>(r#"{"counts": {"a": 1, "b": "x"}}"#)
+            .unwrap_err();
+        assert!(err.to_string().starts_with("key 'b': "), "{}", err);
+    }
+
+    #[test]
+    fn str_like_non_zero() {
+        use std::num::{NonZeroI64, NonZeroU64};
+
+        #[serde_as]
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        struct NonZeroWrapper {
+            #[serde_as(as = "str_like::Wrapper")]
+            id: NonZeroU64,
+            #[serde_as(as = "Option<str_like::Wrapper>")]
+            offset: Option<NonZeroI64>,
+        }
+
+        let wrapper = NonZeroWrapper {
+            id: NonZeroU64::new(u64::MAX).unwrap(),
+            offset: NonZeroI64::new(-7),
+        };
+        let json = serde_json::to_string(&wrapper).unwrap();
+        assert_eq!(json, r#"{"id":"18446744073709551615","offset":"-7"}"#);
+        assert_eq!(wrapper, serde_json::from_str(&json).unwrap());
+
+        for json in [
+            r#"{"id": "0"}"#,
+            r#"{"id": 0}"#,
+            r#"{"id": "1", "offset": "0"}"#,
+        ] {
+            let err = serde_json::from_str::<NonZeroWrapper>(json).unwrap_err();
+            assert!(err.to_string().contains("zero"), "{}: {}", json, err);
+        }
+    }
+
+    #[serde_as]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Int32ValueWrapper {
+        #[serde_as(as = "Option<int32_value::Wrapper>")]
+        signed: Option<i32>,
+        #[serde_as(as = "Option<uint32_value::Wrapper>")]
+        unsigned: Option<u32>,
+    }
+
+    #[test]
+    fn int32_value_present_zero() {
+        let wrapper: Int32ValueWrapper =
+            serde_json::from_str(r#"{"signed": 0, "unsigned": "0"}"#).unwrap();
+        assert_eq!(
+            wrapper,
+            Int32ValueWrapper {
+                signed: Some(0),
+                unsigned: Some(0)
+            }
+        );
+        assert_eq!(
+            serde_json::to_string(&wrapper).unwrap(),
+            r#"{"signed":0,"unsigned":0}"#
+        );
+    }
+
+    #[test]
+    fn int32_value_absent() {
+        let wrapper: Int32ValueWrapper = serde_json::from_str(r#"{"unsigned": null}"#).unwrap();
+        assert_eq!(
+            wrapper,
+            Int32ValueWrapper {
+                signed: None,
+                unsigned: None
+            }
+        );
+    }
+
+    #[test]
+    fn int32_value_out_of_range() {
+        for json in [
+            r#"{"signed": 2147483648}"#,
+            r#"{"unsigned": -1}"#,
+            r#"{"signed": 1.5}"#,
+        ] {
+            assert!(
+                serde_json::from_str::<Int32ValueWrapper>(json).is_err(),
+                "parsed {} expecting err",
+                json
+            );
+        }
+    }
+
+    #[serde_as]
+    #[derive(Serialize, Deserialize, Debug)]
+    struct FloatWrapper {
+        #[serde_as(as = "double_value::Wrapper")]
+        double: f64,
+        #[serde_as(as = "float_value::Wrapper")]
+        float: f32,
+    }
+
+    #[test]
+    fn float_special_values() {
+        for (value, repr) in [
+            (f64::INFINITY, r#""Infinity""#),
+            (f64::NEG_INFINITY, r#""-Infinity""#),
+            (f64::NAN, r#""NaN""#),
+            (1.5, "1.5"),
+            (0.1, "0.1"),
+        ] {
+            let json = format!(r#"{{"double":{},"float":{}}}"#, repr, repr);
+            let wrapper = FloatWrapper {
+                double: value,
+                float: value as f32,
+            };
+            assert_eq!(serde_json::to_string(&wrapper).unwrap(), json);
+
+            let parsed: FloatWrapper = serde_json::from_str(&json).unwrap();
+            if value.is_nan() {
+                assert!(parsed.double.is_nan() && parsed.float.is_nan());
+            } else {
+                assert_eq!(parsed.double, value);
+                assert_eq!(parsed.float, value as f32);
+            }
+        }
+    }
+
+    #[test]
+    fn float_failure_cases() {
+        for repr in [r#""inf""#, r#""infinity""#, r#""nan""#, "true"] {
+            assert!(
+                serde_json::from_str::<FloatWrapper>(&format!(
+                    r#"{{"double":{},"float":1}}"#,
+                    repr
+                ))
+                .is_err(),
+                "parsed {} expecting err",
+                repr
+            );
+        }
+        // finite, but too large for a float
+        assert!(serde_json::from_str::<FloatWrapper>(r#"{"double":1e300,"float":1e300}"#).is_err());
+    }
+
+    /// A day of the week which keeps values it doesn't know.
+    #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash)]
+    #[serde(untagged)]
+    enum LenientDay {
+        Known(DayOfWeek),
+        UnknownName(String),
+        UnknownNumber(i32),
+    }
+
+    #[serde_as]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct EnumSetWrapper {
+        #[serde_as(as = "enum_set::Wrapper")]
+        days: Vec<LenientDay>,
+        #[serde_as(as = "enum_set::Wrapper")]
+        strict: HashSet<DayOfWeek>,
+    }
+
+    #[test]
+    fn enum_keyed_map_roundtrip() {
+        use std::collections::HashMap;
+
+        #[serde_as]
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        struct Schedule {
+            #[serde_as(as = "enum_keyed_map::Wrapper")]
+            hours: HashMap<DayOfWeek, Vec<u32>>,
+        }
+
+        let schedule = Schedule {
+            hours: [
+                (DayOfWeek::Monday, vec![9, 17]),
+                (DayOfWeek::Saturday, vec![]),
+            ]
+            .into_iter()
+            .collect(),
+        };
+        let json = serde_json::to_value(&schedule).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"hours": {"MONDAY": [9, 17], "SATURDAY": []}})
+        );
+        assert_eq!(schedule, serde_json::from_value(json).unwrap());
+
+        let err = serde_json::from_str::<Schedule>(r#"{"hours": {"MONDAY": [], "MONTAG": []}}"#)
+            .unwrap_err();
+        assert!(err.to_string().contains("MONTAG"), "{}", err);
+        let err = serde_json::from_str::<Schedule>(r#"{"hours": {"FRIDAY": ["x"]}}"#).unwrap_err();
+        assert!(err.to_string().starts_with("key 'FRIDAY': "), "{}", err);
+    }
+
+    #[test]
+    fn enum_set_roundtrip() {
+        let wrapper: EnumSetWrapper = serde_json::from_str(
+            r#"{"days": ["MONDAY", 3, "HOLIDAY", "MONDAY", 42, 1], "strict": ["SUNDAY", 7, 1]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            wrapper.days,
+            vec![
+                LenientDay::Known(DayOfWeek::Monday),
+                LenientDay::Known(DayOfWeek::Wednesday),
+                LenientDay::UnknownName("HOLIDAY".to_string()),
+                LenientDay::UnknownNumber(42),
+            ]
+        );
+        assert_eq!(
+            wrapper.strict,
+            HashSet::from([DayOfWeek::Sunday, DayOfWeek::Monday])
+        );
+
+        let json = serde_json::to_value(&wrapper).unwrap();
+        assert_eq!(
+            json["days"],
+            serde_json::json!(["MONDAY", "WEDNESDAY", "HOLIDAY", 42])
+        );
+        assert_eq!(wrapper, serde_json::from_value(json).unwrap());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[serde_as]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct TimestampWrapper {
+        #[serde_as(as = "Option<timestamp::Wrapper>")]
+        timestamp: Option<chrono::DateTime<chrono::Utc>>,
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn timestamp_normalizes_to_utc() {
+        let expected = "2019-12-31T22:00:00Z";
+        for repr in [
+            "2020-01-01T00:00:00+02:00",
+            "2019-12-31T17:00:00-05:00",
+            "2019-12-31t22:00:00z",
+            expected,
+        ] {
+            let wrapper: TimestampWrapper =
+                serde_json::from_str(&format!(r#"{{"timestamp": "{}"}}"#, repr)).unwrap();
+            assert_eq!(
+                serde_json::to_string(&wrapper).unwrap(),
+                format!(r#"{{"timestamp":"{}"}}"#, expected),
+                "parsed \"{}\"",
+                repr
+            );
+        }
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn timestamp_keeps_nanoseconds() {
+        use chrono::TimeZone;
+
+        for (repr, nanos) in [
+            ("1970-01-01T00:00:00.000000001Z", 1),
+            ("1970-01-01T00:00:00.000001Z", 1_000),
+            ("1970-01-01T00:00:00.100Z", 100_000_000),
+            ("1970-01-01T00:00:00.123456789Z", 123_456_789),
+        ] {
+            let json = format!(r#"{{"timestamp":"{}"}}"#, repr);
+            let wrapper: TimestampWrapper = serde_json::from_str(&json).unwrap();
+            assert_eq!(
+                wrapper.timestamp,
+                Some(chrono::Utc.timestamp_opt(0, nanos).unwrap()),
+                "parsed \"{}\"",
+                repr
+            );
+            assert_eq!(serde_json::to_string(&wrapper).unwrap(), json);
+        }
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn timestamp_lenient_accepts_space_separator() {
+        #[serde_as]
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        struct LenientTimestampWrapper {
+            #[serde_as(as = "timestamp_lenient::Wrapper")]
+            timestamp: chrono::DateTime<chrono::Utc>,
+        }
+
+        for (repr, expected) in [
+            ("2020-01-01 00:00:00Z", "2020-01-01T00:00:00Z"),
+            ("2020-01-01 01:00:00.5+01:00", "2020-01-01T00:00:00.500Z"),
+            ("2020-01-01T00:00:00Z", "2020-01-01T00:00:00Z"),
+        ] {
+            let wrapper: LenientTimestampWrapper =
+                serde_json::from_str(&format!(r#"{{"timestamp": "{}"}}"#, repr)).unwrap();
+            assert_eq!(
+                serde_json::to_string(&wrapper).unwrap(),
+                format!(r#"{{"timestamp":"{}"}}"#, expected),
+                "parsed \"{}\"",
+                repr
+            );
+        }
+        assert!(serde_json::from_str::<LenientTimestampWrapper>(
+            r#"{"timestamp": "2020-01-01 00:00:00"}"#
+        )
+        .is_err());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn timestamp_newtype_roundtrip() {
+        use chrono::TimeZone;
+        use timestamp::Timestamp;
+
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        struct Resource {
+            created: Option<Timestamp>,
+        }
+
+        let created = chrono::Utc
+            .timestamp_opt(1_577_836_800, 500_000_000)
+            .unwrap();
+        let resource = Resource {
+            created: Some(created.into()),
+        };
+        let json = serde_json::to_string(&resource).unwrap();
+        assert_eq!(json, r#"{"created":"2020-01-01T00:00:00.500Z"}"#);
+        assert_eq!(resource, serde_json::from_str(&json).unwrap());
+        assert_eq!(
+            Resource { created: None },
+            serde_json::from_str(r#"{"created":null}"#).unwrap()
+        );
+
+        let timestamp = Timestamp(created);
+        assert_eq!(timestamp.to_string(), "2020-01-01T00:00:00.500Z");
+        assert_eq!(timestamp.timestamp(), 1_577_836_800);
+        assert_eq!(chrono::DateTime::<chrono::Utc>::from(timestamp), created);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn timestamp_unix_roundtrip() {
+        use chrono::TimeZone;
+
+        #[serde_as]
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        struct Event {
+            #[serde_as(as = "timestamp_unix::Wrapper")]
+            time: chrono::DateTime<chrono::Utc>,
+        }
+
+        let whole = Event {
+            time: chrono::Utc.timestamp_opt(1_600_000_000, 0).unwrap(),
+        };
+        let json = serde_json::to_string(&whole).unwrap();
+        assert_eq!(json, r#"{"time":1600000000}"#);
+        assert_eq!(whole, serde_json::from_str(&json).unwrap());
+
+        let fractional = Event {
+            time: chrono::Utc
+                .timestamp_opt(1_600_000_000, 250_000_000)
+                .unwrap(),
+        };
+        let json = serde_json::to_string(&fractional).unwrap();
+        assert_eq!(json, r#"{"time":1600000000.25}"#);
+        assert_eq!(fractional, serde_json::from_str(&json).unwrap());
+        assert_eq!(
+            chrono::Utc.timestamp_opt(-2, 500_000_000).unwrap(),
+            serde_json::from_str::<Event>(r#"{"time":-1.5}"#)
+                .unwrap()
+                .time
+        );
+        assert!(serde_json::from_str::<Event>(r#"{"time":"1600000000"}"#).is_err());
+
+        // nanoseconds are rounded to microseconds
+        let nanos = Event {
+            time: chrono::Utc
+                .timestamp_opt(1_600_000_000, 123_456_789)
+                .unwrap(),
+        };
+        let json = serde_json::to_string(&nanos).unwrap();
+        assert_eq!(json, r#"{"time":1600000000.123457}"#);
+        assert_eq!(
+            chrono::Utc
+                .timestamp_opt(1_600_000_000, 123_457_000)
+                .unwrap(),
+            serde_json::from_str::<Event>(&json).unwrap().time
+        );
+        let before_epoch = Event {
+            time: chrono::Utc.timestamp_opt(-2, 499_999_600).unwrap(),
+        };
+        let json = serde_json::to_string(&before_epoch).unwrap();
+        assert_eq!(json, r#"{"time":-1.5}"#);
+        let rounded_up = Event {
+            time: chrono::Utc.timestamp_opt(4, 999_999_500).unwrap(),
+        };
+        assert_eq!(serde_json::to_string(&rounded_up).unwrap(), r#"{"time":5}"#);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn timestamp_failure_cases() {
+        for repr in [
+            "+2020-01-01T00:00:00Z",
+            "2020-01-01 00:00:00Z",
+            "2020-01-01T00:00:00",
+            "2020-01-01",
+        ] {
+            assert!(
+                serde_json::from_str::<TimestampWrapper>(&format!(
+                    r#"{{"timestamp": "{}"}}"#,
+                    repr
+                ))
+                .is_err(),
+                "parsed \"{}\" expecting err",
+                repr
+            );
+        }
+    }
+
+    #[serde_as]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Base64VecWrapper {
+        #[serde_as(as = "urlsafe_base64_vec::Wrapper")]
+        chunks: Vec<Vec<u8>>,
+    }
+
+    #[test]
+    fn urlsafe_base64_vec_roundtrip() {
+        let wrapper = Base64VecWrapper {
+            chunks: vec![b"hello".to_vec(), vec![0xfb, 0xff]],
+        };
+        let json = serde_json::to_string(&wrapper).unwrap();
+        assert_eq!(json, r#"{"chunks":["aGVsbG8=","-_8="]}"#);
+        assert_eq!(wrapper, serde_json::from_str(&json).unwrap());
+
+        let value = serde_json::to_value(&wrapper).unwrap();
+        assert_eq!(wrapper, serde_json::from_value(value).unwrap());
+        let escaped = r#"{"chunks":["aGVsbG8=","-\u005f8="]}"#;
+        assert_eq!(wrapper, serde_json::from_str(escaped).unwrap());
+    }
+
+    #[test]
+    fn urlsafe_base64_vec_names_malformed_element() {
+        let err = serde_json::from_str::<Base64VecWrapper>(r#"{"chunks": ["aGVsbG8=", "a$b"]}"#)
+            .unwrap_err();
+        assert!(err.to_string().starts_with("element 1: "), "{}", err);
+    }
+
+    #[serde_as]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Base64MapWrapper {
+        #[serde_as(as = "urlsafe_base64_map::Wrapper")]
+        blobs: std::collections::HashMap<String, Vec<u8>>,
+    }
+
+    #[test]
+    fn urlsafe_base64_map_roundtrip() {
+        let wrapper = Base64MapWrapper {
+            blobs: [
+                ("greeting".to_string(), b"hello".to_vec()),
+                ("raw".to_string(), vec![0xfb, 0xff]),
+            ]
+            .into_iter()
+            .collect(),
+        };
+        let json = serde_json::to_value(&wrapper).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"blobs": {"greeting": "aGVsbG8=", "raw": "-_8="}})
+        );
+        assert_eq!(wrapper, serde_json::from_value(json).unwrap());
+
+        let err =
+            serde_json::from_str::<Base64MapWrapper>(r#"{"blobs": {"bad": "a$b"}}"#).unwrap_err();
+        assert!(err.to_string().starts_with("key 'bad': "), "{}", err);
+    }
+
+    #[test]
+    fn skip_empty_vec_roundtrip() {
+        #[serde_as]
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        struct SkipEmptyVecWrapper {
+            #[serde_as(as = "skip_empty_vec::Wrapper")]
+            #[serde(default)]
+            nulled: Vec<i32>,
+            #[serde_as(as = "skip_empty_vec::Wrapper")]
+            #[serde(default, skip_serializing_if = "Vec::is_empty")]
+            omitted: Vec<i32>,
+        }
+
+        let empty = SkipEmptyVecWrapper {
+            nulled: Vec::new(),
+            omitted: Vec::new(),
+        };
+        assert_eq!(serde_json::to_string(&empty).unwrap(), r#"{"nulled":null}"#);
+        assert_eq!(empty, serde_json::from_str(r#"{"nulled":null}"#).unwrap());
+        assert_eq!(empty, serde_json::from_str("{}").unwrap());
+
+        let full = SkipEmptyVecWrapper {
+            nulled: vec![1, 2],
+            omitted: vec![3],
+        };
+        let json = serde_json::to_string(&full).unwrap();
+        assert_eq!(json, r#"{"nulled":[1,2],"omitted":[3]}"#);
+        assert_eq!(full, serde_json::from_str(&json).unwrap());
+    }
+
+    crate::proto_oneof! {
+        #[derive(Debug, PartialEq)]
+        enum Source {
+            Uri(String) => "uri",
+            Content(Vec<i32>) => "content",
+        }
+    }
+
+    #[test]
+    fn proto_oneof_roundtrip() {
+        for (source, json) in [
+            (
+                Source::Uri("gs://bucket/a".to_string()),
+                r#"{"uri":"gs://bucket/a"}"#,
+            ),
+            (Source::Content(vec![1, 2]), r#"{"content":[1,2]}"#),
+        ] {
+            assert_eq!(serde_json::to_string(&source).unwrap(), json);
+            assert_eq!(source, serde_json::from_str(json).unwrap());
+        }
+
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        struct Request {
+            name: String,
+            #[serde(flatten)]
+            source: Source,
+        }
+
+        let request = Request {
+            name: "a".to_string(),
+            source: Source::Uri("gs://bucket/a".to_string()),
+        };
+        let json = serde_json::to_string(&request).unwrap();
+        assert_eq!(json, r#"{"name":"a","uri":"gs://bucket/a"}"#);
+        assert_eq!(request, serde_json::from_str(&json).unwrap());
+    }
+
+    #[test]
+    fn proto_oneof_failure_cases() {
+        let err = serde_json::from_str::<Source>(r#"{"uri": "a", "content": []}"#).unwrap_err();
+        assert!(err.to_string().contains("only one of"), "{}", err);
+        let err = serde_json::from_str::<Source>(r#"{"other": 1}"#).unwrap_err();
+        assert!(err.to_string().contains("must be set"), "{}", err);
+    }
+
+    #[test]
+    fn field_mask_array_accepts_both_shapes() {
+        #[serde_as]
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        #[serde(rename_all = "camelCase")]
+        struct FieldMaskArrayWrapper {
+            #[serde_as(as = "field_mask_array::Wrapper")]
+            update_mask: crate::FieldMask,
+        }
+
+        let expected = FieldMaskArrayWrapper {
+            update_mask: "user.display_name,labels.`Some,Key`".parse().unwrap(),
+        };
+        for json in [
+            r#"{"updateMask": "user.displayName,labels.`Some,Key`"}"#,
+            r#"{"updateMask": ["user.displayName", "labels.`Some,Key`"]}"#,
+            r#"{"updateMask": ["user.display_name", "labels.`Some,Key`"]}"#,
+        ] {
+            assert_eq!(
+                expected,
+                serde_json::from_str(json).unwrap(),
+                "parsed {}",
+                json
+            );
+        }
+        assert_eq!(
+            serde_json::to_string(&expected).unwrap(),
+            r#"{"updateMask":"user.displayName,labels.`Some,Key`"}"#
+        );
+        let empty: FieldMaskArrayWrapper = serde_json::from_str(r#"{"updateMask": []}"#).unwrap();
+        assert!(empty.update_mask.is_empty());
+    }
+
+    #[test]
+    fn deserialize_with_context_names_field() {
+        use serde_with::DeserializeAs;
+
+        fn retry_count<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<i32, D::Error> {
+            super::deserialize_with_context(
+                deserializer,
+                "retry_count",
+                int32_value::Wrapper::deserialize_as,
+            )
+        }
+
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct Retry {
+            #[serde(deserialize_with = "retry_count")]
+            count: i32,
+        }
+
+        assert_eq!(
+            serde_json::from_str::<Retry>(r#"{"count": "3"}"#).unwrap(),
+            Retry { count: 3 }
         );
+        let err = serde_json::from_str::<Retry>(r#"{"count": "three"}"#).unwrap_err();
+        assert!(err.to_string().starts_with("retry_count: "), "{}", err);
+    }
+
+    #[serde_as]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct ListValueWrapper {
+        #[serde_as(as = "list_value::Wrapper")]
+        values: Vec<serde_json::Value>,
+    }
+
+    #[test]
+    fn list_value_roundtrip() {
+        let json = r#"{"values":[1,"two",null,true,[3.5,{"four":[]}],{"five":{"six":null}}]}"#;
+        let wrapper: ListValueWrapper = serde_json::from_str(json).unwrap();
+        assert_eq!(wrapper.values.len(), 6);
+        assert_eq!(wrapper.values[4], serde_json::json!([3.5, {"four": []}]));
+        assert_eq!(serde_json::to_string(&wrapper).unwrap(), json);
+
+        let empty = ListValueWrapper { values: Vec::new() };
+        assert_eq!(serde_json::to_string(&empty).unwrap(), r#"{"values":[]}"#);
+        assert!(serde_json::from_str::<ListValueWrapper>(r#"{"values": {}}"#).is_err());
     }
 
     #[test]
     fn test_empty_wrapper() {
+        #[cfg(feature = "chrono")]
         assert_eq!(
             DurationWrapper { duration: None },
             serde_json::from_str("{}").unwrap()
@@ -0,0 +1,180 @@
+//! Common message types of the `google.type` package, as described in
+//! `https://github.com/googleapis/googleapis/tree/master/google/type`, as well as other common
+//! protobuf types which have no counterpart in Rust.
+//!
+//! All types (de)serialize according to the proto3 JSON mapping.
+use std::fmt::{Display, Formatter};
+
+/// The error returned when parsing an enum from a name it doesn't have.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseEnumError {
+    pub enum_name: &'static str,
+    pub value: String,
+}
+
+impl Display for ParseEnumError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "'{}' is not a valid {}", self.value, self.enum_name)
+    }
+}
+
+impl std::error::Error for ParseEnumError {}
+
+/// Defines a proto enum which (de)serializes by its variant names, and additionally accepts its
+/// numeric values on deserialization, as the proto3 JSON mapping requires.
+///
+/// Each variant is given with its numeric value and its name in the proto definition, and the
+/// `*_UNSPECIFIED` zero value should be marked `#[default]`:
+///
+/// ```ignore
+/// google_apis_common::google_type_enum! {
+///     /// A `google.type.CalendarPeriod`.
+///     pub enum CalendarPeriod {
+///         #[default]
+///         Unspecified = 0 => "CALENDAR_PERIOD_UNSPECIFIED",
+///         Day = 1 => "DAY",
+///     }
+/// }
+/// ```
+///
+/// Besides the serde impls, this generates `Display` and `FromStr` by name, along with `as_str`,
+/// `value` and `from_value`.
+#[macro_export]
+macro_rules! google_type_enum {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $(
+                $(#[$variant_meta:meta])*
+                $variant:ident = $value:literal => $repr:literal,
+            )*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        #[repr(i32)]
+        $vis enum $name {
+            $(
+                $(#[$variant_meta])*
+                $variant = $value,
+            )*
+        }
+
+        impl $name {
+            /// Returns the name of the variant as used in the proto definition.
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $($name::$variant => $repr,)*
+                }
+            }
+
+            /// Returns the numeric value of the variant.
+            pub fn value(&self) -> i32 {
+                *self as i32
+            }
+
+            /// Returns the variant with the given numeric value, if any.
+            pub fn from_value(value: i32) -> Option<Self> {
+                match value {
+                    $($value => Some($name::$variant),)*
+                    _ => None,
+                }
+            }
+        }
+
+        impl ::std::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl ::std::str::FromStr for $name {
+            type Err = $crate::types::ParseEnumError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s {
+                    $($repr => Ok($name::$variant),)*
+                    _ => Err($crate::types::ParseEnumError {
+                        enum_name: stringify!($name),
+                        value: s.to_string(),
+                    }),
+                }
+            }
+        }
+
+        impl $crate::__serde::Serialize for $name {
+            fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+            where
+                S: $crate::__serde::Serializer,
+            {
+                s.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> $crate::__serde::Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: $crate::__serde::Deserializer<'de>,
+            {
+                struct Visitor;
+
+                impl<'de> $crate::__serde::de::Visitor<'de> for Visitor {
+                    type Value = $name;
+
+                    fn expecting(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                        write!(f, "a {} name or number", stringify!($name))
+                    }
+
+                    fn visit_str<E: $crate::__serde::de::Error>(self, v: &str) -> Result<$name, E> {
+                        v.parse().map_err(E::custom)
+                    }
+
+                    fn visit_i64<E: $crate::__serde::de::Error>(self, v: i64) -> Result<$name, E> {
+                        i32::try_from(v)
+                            .ok()
+                            .and_then($name::from_value)
+                            .ok_or_else(|| {
+                                E::invalid_value($crate::__serde::de::Unexpected::Signed(v), &self)
+                            })
+                    }
+
+                    fn visit_u64<E: $crate::__serde::de::Error>(self, v: u64) -> Result<$name, E> {
+                        i32::try_from(v)
+                            .ok()
+                            .and_then($name::from_value)
+                            .ok_or_else(|| {
+                                E::invalid_value($crate::__serde::de::Unexpected::Unsigned(v), &self)
+                            })
+                    }
+                }
+
+                deserializer.deserialize_any(Visitor)
+            }
+        }
+    };
+}
+
+#[cfg(feature = "chrono")]
+pub mod any;
+pub mod calendar_period;
+pub mod code;
+#[cfg(feature = "chrono")]
+pub mod datetime;
+pub mod day_of_week;
+#[cfg(feature = "rust_decimal")]
+pub mod decimal;
+pub mod empty;
+pub mod expr;
+pub mod fraction;
+pub mod http_body;
+#[cfg(feature = "chrono")]
+pub mod interval;
+pub mod lat_lng;
+pub mod localized_text;
+pub mod month;
+pub mod null_value;
+pub mod phone_number;
+pub mod postal_address;
+pub mod quaternion;
+pub mod time_zone;
+pub mod viewport;
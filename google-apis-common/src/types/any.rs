@@ -0,0 +1,150 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::de::Error as _;
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Map, Value};
+use serde_with::DeserializeAs;
+
+use crate::serde::{duration, timestamp};
+
+/// The type URL of an embedded `google.protobuf.Duration`.
+pub const DURATION_TYPE_URL: &str = "type.googleapis.com/google.protobuf.Duration";
+/// The type URL of an embedded `google.protobuf.Timestamp`.
+pub const TIMESTAMP_TYPE_URL: &str = "type.googleapis.com/google.protobuf.Timestamp";
+
+/// A `google.protobuf.Any`, a message of any type along with the URL identifying its type.
+///
+/// Messages are represented by their fields next to the `@type` key. Well-known types with a
+/// special JSON form instead carry it in a `value` key, like
+/// `{"@type": "type.googleapis.com/google.protobuf.Duration", "value": "1.5s"}`, which are decoded
+/// for `Duration` and `Timestamp`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Any {
+    Duration(Duration),
+    Timestamp(DateTime<Utc>),
+    /// Any other message, with its fields other than `@type`.
+    Message {
+        type_url: String,
+        fields: Map<String, Value>,
+    },
+}
+
+impl Any {
+    /// Returns the `@type` URL of the message.
+    pub fn type_url(&self) -> &str {
+        match self {
+            Any::Duration(_) => DURATION_TYPE_URL,
+            Any::Timestamp(_) => TIMESTAMP_TYPE_URL,
+            Any::Message { type_url, .. } => type_url,
+        }
+    }
+}
+
+impl Serialize for Any {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = s.serialize_map(None)?;
+        map.serialize_entry("@type", self.type_url())?;
+        match self {
+            Any::Duration(value) => {
+                map.serialize_entry("value", &duration::to_string(value))?;
+            }
+            Any::Timestamp(value) => {
+                map.serialize_entry("value", &timestamp::to_string(value))?;
+            }
+            Any::Message { fields, .. } => {
+                for (key, value) in fields {
+                    map.serialize_entry(key, value)?;
+                }
+            }
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Any {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut fields = Map::deserialize(deserializer)?;
+        let type_url = match fields.remove("@type") {
+            Some(Value::String(type_url)) => type_url,
+            Some(_) => return Err(D::Error::custom("'@type' must be a string")),
+            None => return Err(D::Error::missing_field("@type")),
+        };
+        let value = || {
+            fields
+                .get("value")
+                .ok_or_else(|| D::Error::missing_field("value"))
+        };
+        match type_url.as_str() {
+            DURATION_TYPE_URL => duration::Wrapper::deserialize_as(value()?)
+                .map(Any::Duration)
+                .map_err(D::Error::custom),
+            TIMESTAMP_TYPE_URL => timestamp::Wrapper::deserialize_as(value()?)
+                .map(Any::Timestamp)
+                .map_err(D::Error::custom),
+            _ => Ok(Any::Message { type_url, fields }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Any;
+    use chrono::{Duration, TimeZone, Utc};
+    use serde_json::json;
+
+    #[test]
+    fn any_duration() {
+        let json =
+            json!({"@type": "type.googleapis.com/google.protobuf.Duration", "value": "1.5s"});
+        let any: Any = serde_json::from_value(json).unwrap();
+        assert_eq!(any, Any::Duration(Duration::milliseconds(1500)));
+        assert_eq!(
+            serde_json::to_value(&any).unwrap(),
+            json!({"@type": "type.googleapis.com/google.protobuf.Duration", "value": "1.500s"})
+        );
+    }
+
+    #[test]
+    fn any_timestamp() {
+        let json = json!({
+            "@type": "type.googleapis.com/google.protobuf.Timestamp",
+            "value": "2020-01-01T00:00:00.500Z",
+        });
+        let any: Any = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(
+            any,
+            Any::Timestamp(Utc.timestamp_opt(1_577_836_800, 500_000_000).unwrap())
+        );
+        assert_eq!(serde_json::to_value(&any).unwrap(), json);
+    }
+
+    #[test]
+    fn any_message() {
+        let json = json!({"@type": "type.googleapis.com/google.rpc.ErrorInfo", "reason": "QUOTA"});
+        let any: Any = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(any.type_url(), "type.googleapis.com/google.rpc.ErrorInfo");
+        assert_eq!(serde_json::to_value(&any).unwrap(), json);
+    }
+
+    #[test]
+    fn any_failure_cases() {
+        for json in [
+            json!({"value": "1s"}),
+            json!({"@type": "type.googleapis.com/google.protobuf.Duration"}),
+            json!({"@type": "type.googleapis.com/google.protobuf.Duration", "value": "1"}),
+            json!({"@type": "type.googleapis.com/google.protobuf.Timestamp", "value": 0}),
+        ] {
+            assert!(
+                serde_json::from_value::<Any>(json.clone()).is_err(),
+                "{}",
+                json
+            );
+        }
+    }
+}
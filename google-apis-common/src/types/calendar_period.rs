@@ -0,0 +1,50 @@
+google_type_enum! {
+    /// A `google.type.CalendarPeriod`, a unit of calendar time which starts and ends on calendar
+    /// boundaries, unlike a fixed duration.
+    pub enum CalendarPeriod {
+        /// The calendar period is unspecified.
+        #[default]
+        Unspecified = 0 => "CALENDAR_PERIOD_UNSPECIFIED",
+        /// A day, in the time zone of the context.
+        Day = 1 => "DAY",
+        /// A week starting on Monday, as defined by ISO 8601.
+        Week = 2 => "WEEK",
+        /// A fortnight, whose first week starts on the first Monday of the year.
+        Fortnight = 3 => "FORTNIGHT",
+        Month = 4 => "MONTH",
+        /// A quarter starting on the 1st of January, April, July or October.
+        Quarter = 5 => "QUARTER",
+        /// A half-year starting on the 1st of January or July.
+        Half = 6 => "HALF",
+        Year = 7 => "YEAR",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::CalendarPeriod;
+
+    #[test]
+    fn calendar_period_roundtrip() {
+        let json = serde_json::to_string(&CalendarPeriod::Fortnight).unwrap();
+        assert_eq!(json, r#""FORTNIGHT""#);
+        assert_eq!(
+            CalendarPeriod::Fortnight,
+            serde_json::from_str(&json).unwrap()
+        );
+        assert_eq!(
+            CalendarPeriod::default().to_string(),
+            "CALENDAR_PERIOD_UNSPECIFIED"
+        );
+        assert_eq!("QUARTER".parse(), Ok(CalendarPeriod::Quarter));
+    }
+
+    #[test]
+    fn calendar_period_from_integer() {
+        assert_eq!(
+            CalendarPeriod::Month,
+            serde_json::from_str::<CalendarPeriod>("4").unwrap()
+        );
+        assert!(serde_json::from_str::<CalendarPeriod>("8").is_err());
+    }
+}
@@ -0,0 +1,184 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::types::ParseEnumError;
+
+/// A `google.rpc.Code`, the canonical error codes of Google APIs.
+///
+/// It serializes as its number, as used by `google.rpc.Status`, and deserializes from its number
+/// or name. [`Display`] and [`FromStr`] use the canonical names, like `NOT_FOUND`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Code {
+    Ok,
+    Cancelled,
+    Unknown,
+    InvalidArgument,
+    DeadlineExceeded,
+    NotFound,
+    AlreadyExists,
+    PermissionDenied,
+    ResourceExhausted,
+    FailedPrecondition,
+    Aborted,
+    OutOfRange,
+    Unimplemented,
+    Internal,
+    Unavailable,
+    DataLoss,
+    Unauthenticated,
+    /// A code which is not part of the canonical set, kept as is. Not to be confused with
+    /// [`Code::Unknown`], which is a canonical code itself.
+    Unrecognized(i32),
+}
+
+/// The canonical codes, with their index being their number.
+const CODES: [(Code, &str); 17] = [
+    (Code::Ok, "OK"),
+    (Code::Cancelled, "CANCELLED"),
+    (Code::Unknown, "UNKNOWN"),
+    (Code::InvalidArgument, "INVALID_ARGUMENT"),
+    (Code::DeadlineExceeded, "DEADLINE_EXCEEDED"),
+    (Code::NotFound, "NOT_FOUND"),
+    (Code::AlreadyExists, "ALREADY_EXISTS"),
+    (Code::PermissionDenied, "PERMISSION_DENIED"),
+    (Code::ResourceExhausted, "RESOURCE_EXHAUSTED"),
+    (Code::FailedPrecondition, "FAILED_PRECONDITION"),
+    (Code::Aborted, "ABORTED"),
+    (Code::OutOfRange, "OUT_OF_RANGE"),
+    (Code::Unimplemented, "UNIMPLEMENTED"),
+    (Code::Internal, "INTERNAL"),
+    (Code::Unavailable, "UNAVAILABLE"),
+    (Code::DataLoss, "DATA_LOSS"),
+    (Code::Unauthenticated, "UNAUTHENTICATED"),
+];
+
+impl Code {
+    /// Returns the code with the given number, which is [`Code::Unrecognized`] for numbers
+    /// outside of the canonical set.
+    pub fn from_value(value: i32) -> Code {
+        usize::try_from(value)
+            .ok()
+            .and_then(|index| CODES.get(index))
+            .map_or(Code::Unrecognized(value), |(code, _)| *code)
+    }
+
+    /// Returns the number of the code.
+    pub fn value(&self) -> i32 {
+        match self {
+            Code::Unrecognized(value) => *value,
+            code => CODES
+                .iter()
+                .position(|(c, _)| c == code)
+                .expect("all canonical codes are listed") as i32,
+        }
+    }
+
+    /// Returns the canonical name of the code, or `None` if it is unrecognized.
+    pub fn name(&self) -> Option<&'static str> {
+        CODES
+            .iter()
+            .find(|(code, _)| code == self)
+            .map(|(_, name)| *name)
+    }
+}
+
+impl Display for Code {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.name() {
+            Some(name) => f.write_str(name),
+            None => write!(f, "{}", self.value()),
+        }
+    }
+}
+
+impl FromStr for Code {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        CODES
+            .iter()
+            .find(|(_, name)| *name == s)
+            .map(|(code, _)| *code)
+            .ok_or_else(|| ParseEnumError {
+                enum_name: "Code",
+                value: s.to_string(),
+            })
+    }
+}
+
+impl Serialize for Code {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        s.serialize_i32(self.value())
+    }
+}
+
+impl<'de> Deserialize<'de> for Code {
+    fn deserialize<D>(deserializer: D) -> Result<Code, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = Code;
+
+            fn expecting(&self, f: &mut Formatter) -> std::fmt::Result {
+                f.write_str("a google.rpc.Code number or name")
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<Code, E> {
+                i32::try_from(v)
+                    .map(Code::from_value)
+                    .map_err(|_| E::invalid_value(de::Unexpected::Signed(v), &self))
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<Code, E> {
+                i32::try_from(v)
+                    .map(Code::from_value)
+                    .map_err(|_| E::invalid_value(de::Unexpected::Unsigned(v), &self))
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Code, E> {
+                v.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(Visitor)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Code;
+
+    #[test]
+    fn code_integer_roundtrip() {
+        for value in 0..17 {
+            let code: Code = serde_json::from_str(&value.to_string()).unwrap();
+            assert!(code.name().is_some(), "{} should be canonical", value);
+            assert_eq!(serde_json::to_string(&code).unwrap(), value.to_string());
+        }
+        let code: Code = serde_json::from_str("42").unwrap();
+        assert_eq!(code, Code::Unrecognized(42));
+        assert_eq!(serde_json::to_string(&code).unwrap(), "42");
+        assert_eq!(code.to_string(), "42");
+    }
+
+    #[test]
+    fn code_names() {
+        assert_eq!("NOT_FOUND".parse(), Ok(Code::NotFound));
+        assert_eq!("UNKNOWN".parse(), Ok(Code::Unknown));
+        assert_eq!(Code::Unauthenticated.to_string(), "UNAUTHENTICATED");
+        assert_eq!(Code::Unauthenticated.value(), 16);
+        assert!("not_found".parse::<Code>().is_err());
+        assert_eq!(
+            serde_json::from_str::<Code>(r#""PERMISSION_DENIED""#).unwrap(),
+            Code::PermissionDenied
+        );
+    }
+}
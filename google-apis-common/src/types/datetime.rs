@@ -0,0 +1,234 @@
+use std::fmt::{Display, Formatter};
+
+use chrono::{Datelike, Duration, FixedOffset, NaiveDate, Timelike};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+use crate::serde::duration;
+use crate::types::time_zone::TimeZone;
+
+/// The time offset of a [`DateTime`], the `time_offset` oneof of `google.type.DateTime`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TimeOffset {
+    /// A fixed offset from UTC, e.g. `-4h` for `UTC-4`.
+    UtcOffset(Duration),
+    /// An IANA time zone.
+    TimeZone(TimeZone),
+}
+
+/// A `google.type.DateTime`, a civil time with an optional offset or time zone.
+///
+/// A `year` of 0 signifies a date without a year, and a missing `time_offset` signifies local
+/// time.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "DateTimeRepr", into = "DateTimeRepr")]
+pub struct DateTime {
+    pub year: i32,
+    pub month: i32,
+    pub day: i32,
+    pub hours: i32,
+    pub minutes: i32,
+    pub seconds: i32,
+    pub nanos: i32,
+    pub time_offset: Option<TimeOffset>,
+}
+
+impl DateTime {
+    /// Returns the equivalent `chrono` date time, which is possible if the date time has a year,
+    /// all fields are in range, and it carries a UTC offset of whole seconds.
+    pub fn to_chrono(&self) -> Option<chrono::DateTime<FixedOffset>> {
+        let offset = match &self.time_offset {
+            Some(TimeOffset::UtcOffset(offset)) => offset,
+            _ => return None,
+        };
+        if self.year == 0 || offset.num_nanoseconds()? % 1_000_000_000 != 0 {
+            return None;
+        }
+        let offset = FixedOffset::east_opt(i32::try_from(offset.num_seconds()).ok()?)?;
+        NaiveDate::from_ymd_opt(
+            self.year,
+            u32::try_from(self.month).ok()?,
+            u32::try_from(self.day).ok()?,
+        )?
+        .and_hms_nano_opt(
+            u32::try_from(self.hours).ok()?,
+            u32::try_from(self.minutes).ok()?,
+            u32::try_from(self.seconds).ok()?,
+            u32::try_from(self.nanos).ok()?,
+        )?
+        .and_local_timezone(offset)
+        .single()
+    }
+}
+
+impl From<chrono::DateTime<FixedOffset>> for DateTime {
+    fn from(datetime: chrono::DateTime<FixedOffset>) -> Self {
+        DateTime {
+            year: datetime.year(),
+            month: datetime.month() as i32,
+            day: datetime.day() as i32,
+            hours: datetime.hour() as i32,
+            minutes: datetime.minute() as i32,
+            seconds: datetime.second() as i32,
+            nanos: datetime.nanosecond() as i32,
+            time_offset: Some(TimeOffset::UtcOffset(Duration::seconds(
+                datetime.offset().local_minus_utc().into(),
+            ))),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum DateTimeError {
+    /// Both `utcOffset` and `timeZone` were set, but they are mutually exclusive.
+    ConflictingTimeOffset,
+}
+
+impl Display for DateTimeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DateTimeError::ConflictingTimeOffset => {
+                f.write_str("only one of 'utcOffset' and 'timeZone' may be set")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DateTimeError {}
+
+/// The wire representation of a [`DateTime`], with the oneof members as separate fields.
+#[serde_as]
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DateTimeRepr {
+    #[serde(default)]
+    year: i32,
+    #[serde(default)]
+    month: i32,
+    #[serde(default)]
+    day: i32,
+    #[serde(default)]
+    hours: i32,
+    #[serde(default)]
+    minutes: i32,
+    #[serde(default)]
+    seconds: i32,
+    #[serde(default)]
+    nanos: i32,
+    #[serde_as(as = "Option<duration::Wrapper>")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    utc_offset: Option<Duration>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    time_zone: Option<TimeZone>,
+}
+
+impl TryFrom<DateTimeRepr> for DateTime {
+    type Error = DateTimeError;
+
+    fn try_from(repr: DateTimeRepr) -> Result<Self, Self::Error> {
+        let time_offset = match (repr.utc_offset, repr.time_zone) {
+            (Some(_), Some(_)) => return Err(DateTimeError::ConflictingTimeOffset),
+            (Some(offset), None) => Some(TimeOffset::UtcOffset(offset)),
+            (None, Some(time_zone)) => Some(TimeOffset::TimeZone(time_zone)),
+            (None, None) => None,
+        };
+        Ok(DateTime {
+            year: repr.year,
+            month: repr.month,
+            day: repr.day,
+            hours: repr.hours,
+            minutes: repr.minutes,
+            seconds: repr.seconds,
+            nanos: repr.nanos,
+            time_offset,
+        })
+    }
+}
+
+impl From<DateTime> for DateTimeRepr {
+    fn from(datetime: DateTime) -> Self {
+        let (utc_offset, time_zone) = match datetime.time_offset {
+            Some(TimeOffset::UtcOffset(offset)) => (Some(offset), None),
+            Some(TimeOffset::TimeZone(time_zone)) => (None, Some(time_zone)),
+            None => (None, None),
+        };
+        DateTimeRepr {
+            year: datetime.year,
+            month: datetime.month,
+            day: datetime.day,
+            hours: datetime.hours,
+            minutes: datetime.minutes,
+            seconds: datetime.seconds,
+            nanos: datetime.nanos,
+            utc_offset,
+            time_zone,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{DateTime, TimeOffset};
+    use crate::types::time_zone::TimeZone;
+    use chrono::Duration;
+
+    fn civil_time(time_offset: Option<TimeOffset>) -> DateTime {
+        DateTime {
+            year: 2020,
+            month: 1,
+            day: 2,
+            hours: 3,
+            minutes: 4,
+            seconds: 5,
+            nanos: 6,
+            time_offset,
+        }
+    }
+
+    #[test]
+    fn utc_offset_roundtrip() {
+        let datetime = civil_time(Some(TimeOffset::UtcOffset(Duration::hours(-4))));
+        let json = serde_json::to_string(&datetime).unwrap();
+        assert_eq!(
+            json,
+            r#"{"year":2020,"month":1,"day":2,"hours":3,"minutes":4,"seconds":5,"nanos":6,"utcOffset":"-14400s"}"#
+        );
+        assert_eq!(datetime, serde_json::from_str(&json).unwrap());
+
+        let chrono = datetime.to_chrono().unwrap();
+        assert_eq!(chrono.to_rfc3339(), "2020-01-02T03:04:05.000000006-04:00");
+        assert_eq!(DateTime::from(chrono), datetime);
+    }
+
+    #[test]
+    fn time_zone_roundtrip() {
+        let datetime = civil_time(Some(TimeOffset::TimeZone(TimeZone {
+            id: "America/New_York".to_string(),
+            version: None,
+        })));
+        let json = serde_json::to_string(&datetime).unwrap();
+        assert!(json.ends_with(r#""timeZone":{"id":"America/New_York"}}"#));
+        assert_eq!(datetime, serde_json::from_str(&json).unwrap());
+        assert_eq!(datetime.to_chrono(), None);
+    }
+
+    #[test]
+    fn local_time_roundtrip() {
+        let datetime = civil_time(None);
+        let json = serde_json::to_string(&datetime).unwrap();
+        assert_eq!(datetime, serde_json::from_str(&json).unwrap());
+        assert_eq!(
+            DateTime::default(),
+            serde_json::from_str::<DateTime>("{}").unwrap()
+        );
+    }
+
+    #[test]
+    fn conflicting_time_offset() {
+        let err = serde_json::from_str::<DateTime>(
+            r#"{"year": 2020, "utcOffset": "3600s", "timeZone": {"id": "Europe/Berlin"}}"#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("only one of"), "{}", err);
+    }
+}
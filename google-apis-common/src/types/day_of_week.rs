@@ -0,0 +1,38 @@
+google_type_enum! {
+    /// A `google.type.DayOfWeek`.
+    pub enum DayOfWeek {
+        /// The day of the week is unspecified.
+        #[default]
+        Unspecified = 0 => "DAY_OF_WEEK_UNSPECIFIED",
+        Monday = 1 => "MONDAY",
+        Tuesday = 2 => "TUESDAY",
+        Wednesday = 3 => "WEDNESDAY",
+        Thursday = 4 => "THURSDAY",
+        Friday = 5 => "FRIDAY",
+        Saturday = 6 => "SATURDAY",
+        Sunday = 7 => "SUNDAY",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DayOfWeek;
+
+    #[test]
+    fn day_of_week_roundtrip() {
+        let json = serde_json::to_string(&DayOfWeek::Friday).unwrap();
+        assert_eq!(json, r#""FRIDAY""#);
+        assert_eq!(DayOfWeek::Friday, serde_json::from_str(&json).unwrap());
+        assert_eq!(DayOfWeek::Sunday.to_string(), "SUNDAY");
+        assert_eq!("MONDAY".parse(), Ok(DayOfWeek::Monday));
+    }
+
+    #[test]
+    fn day_of_week_from_integer() {
+        assert_eq!(
+            DayOfWeek::Sunday,
+            serde_json::from_str::<DayOfWeek>("7").unwrap()
+        );
+        assert!(serde_json::from_str::<DayOfWeek>("-1").is_err());
+    }
+}
@@ -0,0 +1,98 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+/// A `google.type.Decimal`, an arbitrary precision decimal number carried as a string.
+///
+/// The scale of the number is preserved, so `1.50` round-trips as `1.50`. The scientific notation
+/// permitted by the specification, like `1E3` or `-2.5e-3`, is accepted on deserialization.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Decimal(pub rust_decimal::Decimal);
+
+impl From<rust_decimal::Decimal> for Decimal {
+    fn from(decimal: rust_decimal::Decimal) -> Self {
+        Decimal(decimal)
+    }
+}
+
+impl From<Decimal> for rust_decimal::Decimal {
+    fn from(decimal: Decimal) -> Self {
+        decimal.0
+    }
+}
+
+impl FromStr for Decimal {
+    type Err = rust_decimal::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.contains(['e', 'E']) {
+            rust_decimal::Decimal::from_scientific(s).map(Decimal)
+        } else {
+            rust_decimal::Decimal::from_str(s).map(Decimal)
+        }
+    }
+}
+
+impl Display for Decimal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct DecimalRepr<T> {
+    value: T,
+}
+
+impl Serialize for Decimal {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        DecimalRepr {
+            value: self.to_string(),
+        }
+        .serialize(s)
+    }
+}
+
+impl<'de> Deserialize<'de> for Decimal {
+    fn deserialize<D>(deserializer: D) -> Result<Decimal, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let repr: DecimalRepr<String> = Deserialize::deserialize(deserializer)?;
+        Decimal::from_str(&repr.value).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Decimal;
+    use std::str::FromStr;
+
+    #[test]
+    fn decimal_roundtrip() {
+        for repr in ["1.5", "1.50", "-12.345", "0"] {
+            let json = format!(r#"{{"value":"{}"}}"#, repr);
+            let decimal: Decimal = serde_json::from_str(&json).unwrap();
+            assert_eq!(decimal.0, rust_decimal::Decimal::from_str(repr).unwrap());
+            assert_eq!(serde_json::to_string(&decimal).unwrap(), json);
+        }
+    }
+
+    #[test]
+    fn decimal_scientific_notation() {
+        let decimal: Decimal = serde_json::from_str(r#"{"value": "1E3"}"#).unwrap();
+        assert_eq!(decimal.0, rust_decimal::Decimal::from(1000));
+        let decimal: Decimal = serde_json::from_str(r#"{"value": "-2.5e-3"}"#).unwrap();
+        assert_eq!(decimal.to_string(), "-0.0025");
+    }
+
+    #[test]
+    fn decimal_failure_cases() {
+        assert!(serde_json::from_str::<Decimal>(r#"{"value": "1.2.3"}"#).is_err());
+        assert!(serde_json::from_str::<Decimal>(r#"{"value": 1.5}"#).is_err());
+    }
+}
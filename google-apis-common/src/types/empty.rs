@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+/// A `google.protobuf.Empty`, used as the request or response type of methods which don't have
+/// one otherwise.
+///
+/// It serializes as `{}`, and deserializes from any object, ignoring its fields.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Empty {}
+
+#[cfg(test)]
+mod test {
+    use super::Empty;
+
+    #[test]
+    fn empty_roundtrip() {
+        assert_eq!(serde_json::to_string(&Empty {}).unwrap(), "{}");
+        assert_eq!(serde_json::from_str::<Empty>("{}").unwrap(), Empty {});
+        assert_eq!(
+            serde_json::from_str::<Empty>(r#"{"ignored": 1}"#).unwrap(),
+            Empty {}
+        );
+        assert!(serde_json::from_str::<Empty>("null").is_err());
+    }
+}
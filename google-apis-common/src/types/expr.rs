@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+
+/// A `google.type.Expr`, a textual expression in the Common Expression Language (CEL), as used
+/// by IAM conditions.
+///
+/// Unknown fields are ignored on deserialization.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Expr {
+    /// The expression, e.g. `request.time < timestamp("2020-01-01T00:00:00Z")`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expression: Option<String>,
+    /// A short title of the expression, e.g. for display in a UI.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The location of the expression for error reporting, e.g. a file name and position.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::Expr;
+
+    #[test]
+    fn expr_roundtrip() {
+        let expr = Expr {
+            expression: Some(r#"resource.name.startsWith("projects/_/buckets/a")"#.to_string()),
+            title: Some("Bucket a".to_string()),
+            description: Some("Only objects in bucket a".to_string()),
+            location: Some("policy.json:12".to_string()),
+        };
+        let json = serde_json::to_value(&expr).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "expression": "resource.name.startsWith(\"projects/_/buckets/a\")",
+                "title": "Bucket a",
+                "description": "Only objects in bucket a",
+                "location": "policy.json:12",
+            })
+        );
+        assert_eq!(expr, serde_json::from_value(json).unwrap());
+        assert_eq!(
+            Expr {
+                title: Some("t".to_string()),
+                ..Default::default()
+            },
+            serde_json::from_str(r#"{"title": "t", "unknown": 1}"#).unwrap()
+        );
+    }
+}
@@ -0,0 +1,91 @@
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+use crate::serde::str_like;
+
+/// A `google.type.Fraction`, a fraction in terms of a numerator divided by a denominator.
+///
+/// Deserialization fails if the denominator is zero.
+#[serde_as]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "FractionRepr")]
+pub struct Fraction {
+    #[serde_as(as = "str_like::Wrapper")]
+    pub numerator: i64,
+    #[serde_as(as = "str_like::Wrapper")]
+    pub denominator: i64,
+}
+
+impl Fraction {
+    /// Returns the value of the fraction as a floating point number.
+    pub fn to_f64(&self) -> f64 {
+        self.numerator as f64 / self.denominator as f64
+    }
+}
+
+#[derive(Debug)]
+pub enum FractionError {
+    ZeroDenominator,
+}
+
+impl Display for FractionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FractionError::ZeroDenominator => f.write_str("the denominator must not be zero"),
+        }
+    }
+}
+
+impl std::error::Error for FractionError {}
+
+#[serde_as]
+#[derive(Deserialize)]
+struct FractionRepr {
+    #[serde_as(as = "str_like::Wrapper")]
+    #[serde(default)]
+    numerator: i64,
+    #[serde_as(as = "str_like::Wrapper")]
+    #[serde(default)]
+    denominator: i64,
+}
+
+impl TryFrom<FractionRepr> for Fraction {
+    type Error = FractionError;
+
+    fn try_from(repr: FractionRepr) -> Result<Self, Self::Error> {
+        if repr.denominator == 0 {
+            return Err(FractionError::ZeroDenominator);
+        }
+        Ok(Fraction {
+            numerator: repr.numerator,
+            denominator: repr.denominator,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Fraction;
+
+    #[test]
+    fn fraction_roundtrip() {
+        let fraction = Fraction {
+            numerator: 3,
+            denominator: 4,
+        };
+        let json = serde_json::to_string(&fraction).unwrap();
+        assert_eq!(json, r#"{"numerator":"3","denominator":"4"}"#);
+        assert_eq!(fraction, serde_json::from_str(&json).unwrap());
+        assert_eq!(fraction.to_f64(), 0.75);
+    }
+
+    #[test]
+    fn zero_denominator() {
+        assert!(
+            serde_json::from_str::<Fraction>(r#"{"numerator": "1", "denominator": "0"}"#).is_err()
+        );
+        assert!(serde_json::from_str::<Fraction>(r#"{"numerator": "1"}"#).is_err());
+    }
+}
@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use serde_with::serde_as;
+
+use crate::serde::standard_base64;
+
+/// A `google.api.HttpBody`, an arbitrary HTTP body used by methods whose request or response
+/// isn't JSON, like raw binary uploads and downloads.
+#[serde_as]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpBody {
+    /// The HTTP `Content-Type` of the body, e.g. `image/png`.
+    #[serde(default)]
+    pub content_type: String,
+    /// The raw body, serialized as standard base64.
+    #[serde_as(as = "standard_base64::Wrapper")]
+    #[serde(default)]
+    pub data: Vec<u8>,
+    /// Application specific response metadata, as `google.protobuf.Any` messages.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extensions: Vec<Value>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::HttpBody;
+
+    #[test]
+    fn http_body_roundtrip() {
+        let body = HttpBody {
+            content_type: "application/octet-stream".to_string(),
+            data: vec![0x00, 0xfb, 0xff, 0x3e, 0x7f],
+            extensions: Vec::new(),
+        };
+        let json = serde_json::to_string(&body).unwrap();
+        assert_eq!(
+            json,
+            r#"{"contentType":"application/octet-stream","data":"APv/Pn8="}"#
+        );
+        assert_eq!(body, serde_json::from_str(&json).unwrap());
+        assert_eq!(
+            HttpBody::default(),
+            serde_json::from_str::<HttpBody>("{}").unwrap()
+        );
+    }
+}
@@ -0,0 +1,74 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+use crate::serde::timestamp;
+
+/// Returns the duration from `start` to `end`, which is negative if `end` precedes `start`.
+///
+/// For timestamps within the range of `google.protobuf.Timestamp`, the result is always
+/// within the range of `google.protobuf.Duration`.
+pub fn duration_between(start: &DateTime<Utc>, end: &DateTime<Utc>) -> Duration {
+    end.signed_duration_since(*start)
+}
+
+/// A `google.type.Interval`, a time interval with an inclusive start and exclusive end.
+///
+/// A missing `start_time` means the interval is unbounded at the start, and a missing
+/// `end_time` means it is unbounded at the end.
+#[serde_as]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Interval {
+    #[serde_as(as = "Option<timestamp::Wrapper>")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_time: Option<DateTime<Utc>>,
+    #[serde_as(as = "Option<timestamp::Wrapper>")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_time: Option<DateTime<Utc>>,
+}
+
+impl Interval {
+    /// Returns the duration of the interval, if it is bounded on both ends.
+    pub fn duration(&self) -> Option<Duration> {
+        Some(duration_between(
+            self.start_time.as_ref()?,
+            self.end_time.as_ref()?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{duration_between, Interval};
+    use chrono::{Duration, TimeZone, Utc};
+
+    #[test]
+    fn duration_between_timestamps() {
+        let start = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2020, 1, 2, 0, 0, 1).unwrap();
+        assert_eq!(
+            duration_between(&start, &end),
+            Duration::days(1) + Duration::seconds(1)
+        );
+        assert_eq!(duration_between(&start, &start), Duration::zero());
+        assert_eq!(duration_between(&end, &start), -Duration::seconds(86401));
+    }
+
+    #[test]
+    fn interval_duration() {
+        let interval: Interval = serde_json::from_str(
+            r#"{"startTime": "2020-01-01T00:00:00Z", "endTime": "2020-01-01T00:00:01.5Z"}"#,
+        )
+        .unwrap();
+        assert_eq!(interval.duration(), Some(Duration::milliseconds(1500)));
+
+        let unbounded: Interval =
+            serde_json::from_str(r#"{"startTime": "2020-01-01T00:00:00Z"}"#).unwrap();
+        assert_eq!(unbounded.duration(), None);
+        assert_eq!(
+            serde_json::to_string(&unbounded).unwrap(),
+            r#"{"startTime":"2020-01-01T00:00:00Z"}"#
+        );
+    }
+}
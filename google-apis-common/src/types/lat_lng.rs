@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+use crate::serde::double_value;
+
+/// A `google.type.LatLng`, a latitude and longitude pair in degrees of the WGS84 standard.
+///
+/// Omitted components default to 0.
+#[serde_as]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct LatLng {
+    /// The latitude in degrees, in the range `[-90.0, +90.0]`.
+    #[serde_as(as = "double_value::Wrapper")]
+    #[serde(default)]
+    pub latitude: f64,
+    /// The longitude in degrees, in the range `[-180.0, +180.0]`.
+    #[serde_as(as = "double_value::Wrapper")]
+    #[serde(default)]
+    pub longitude: f64,
+}
+
+#[cfg(test)]
+mod test {
+    use super::LatLng;
+
+    #[test]
+    fn lat_lng_roundtrip() {
+        let lat_lng = LatLng {
+            latitude: 47.3769,
+            longitude: 8.5417,
+        };
+        let json = serde_json::to_string(&lat_lng).unwrap();
+        assert_eq!(json, r#"{"latitude":47.3769,"longitude":8.5417}"#);
+        assert_eq!(lat_lng, serde_json::from_str(&json).unwrap());
+        assert_eq!(LatLng::default(), serde_json::from_str("{}").unwrap());
+    }
+}
@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+/// A `google.type.LocalizedText`, a user-facing string along with its language.
+///
+/// A missing `languageCode` defaults to an empty string.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalizedText {
+    /// The text in the language given by `language_code`.
+    #[serde(default)]
+    pub text: String,
+    /// The BCP-47 language code of the text, e.g. `en-US` or `sr-Latn`.
+    #[serde(default, alias = "language_code")]
+    pub language_code: String,
+}
+
+#[cfg(test)]
+mod test {
+    use super::LocalizedText;
+
+    #[test]
+    fn localized_text_roundtrip() {
+        let text = LocalizedText {
+            text: "Hallo Welt".to_string(),
+            language_code: "de-CH".to_string(),
+        };
+        let json = serde_json::to_string(&text).unwrap();
+        assert_eq!(json, r#"{"text":"Hallo Welt","languageCode":"de-CH"}"#);
+        assert_eq!(text, serde_json::from_str(&json).unwrap());
+        assert_eq!(
+            text,
+            serde_json::from_str(r#"{"text":"Hallo Welt","language_code":"de-CH"}"#).unwrap()
+        );
+
+        let parsed: LocalizedText = serde_json::from_str(r#"{"text":"Hello"}"#).unwrap();
+        assert_eq!(parsed.language_code, "");
+    }
+}
@@ -0,0 +1,52 @@
+google_type_enum! {
+    /// A `google.type.Month` of the Gregorian calendar.
+    pub enum Month {
+        /// The unspecified month.
+        #[default]
+        Unspecified = 0 => "MONTH_UNSPECIFIED",
+        January = 1 => "JANUARY",
+        February = 2 => "FEBRUARY",
+        March = 3 => "MARCH",
+        April = 4 => "APRIL",
+        May = 5 => "MAY",
+        June = 6 => "JUNE",
+        July = 7 => "JULY",
+        August = 8 => "AUGUST",
+        September = 9 => "SEPTEMBER",
+        October = 10 => "OCTOBER",
+        November = 11 => "NOVEMBER",
+        December = 12 => "DECEMBER",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Month;
+
+    #[test]
+    fn month_roundtrip() {
+        assert_eq!(serde_json::to_string(&Month::March).unwrap(), r#""MARCH""#);
+        assert_eq!(
+            Month::March,
+            serde_json::from_str::<Month>(r#""MARCH""#).unwrap()
+        );
+        assert_eq!(
+            Month::Unspecified,
+            serde_json::from_str::<Month>(r#""MONTH_UNSPECIFIED""#).unwrap()
+        );
+    }
+
+    #[test]
+    fn month_from_integer() {
+        assert_eq!(
+            Month::December,
+            serde_json::from_str::<Month>("12").unwrap()
+        );
+        assert_eq!(
+            Month::Unspecified,
+            serde_json::from_str::<Month>("0").unwrap()
+        );
+        assert!(serde_json::from_str::<Month>("13").is_err());
+        assert!(serde_json::from_str::<Month>(r#""March""#).is_err());
+    }
+}
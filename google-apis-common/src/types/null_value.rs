@@ -0,0 +1,93 @@
+use std::fmt::Formatter;
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+/// A `google.protobuf.NullValue`, the enum with the single variant `NULL_VALUE`, which is the
+/// JSON `null`.
+///
+/// It serializes as `null`, and deserializes from `null` as well as from the variant's name
+/// `"NULL_VALUE"` and number `0`, as for any proto enum.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct NullValue;
+
+impl Serialize for NullValue {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        s.serialize_unit()
+    }
+}
+
+struct Visitor;
+
+impl<'de> de::Visitor<'de> for Visitor {
+    type Value = NullValue;
+
+    fn expecting(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "null, \"NULL_VALUE\" or 0")
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<NullValue, E> {
+        Ok(NullValue)
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<NullValue, E> {
+        Ok(NullValue)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<NullValue, E> {
+        match v {
+            "NULL_VALUE" => Ok(NullValue),
+            _ => Err(E::invalid_value(de::Unexpected::Str(v), &self)),
+        }
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<NullValue, E> {
+        match v {
+            0 => Ok(NullValue),
+            _ => Err(E::invalid_value(de::Unexpected::Signed(v), &self)),
+        }
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<NullValue, E> {
+        match v {
+            0 => Ok(NullValue),
+            _ => Err(E::invalid_value(de::Unexpected::Unsigned(v), &self)),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for NullValue {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(Visitor)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::NullValue;
+
+    #[test]
+    fn null_value_roundtrip() {
+        assert_eq!(serde_json::to_string(&NullValue).unwrap(), "null");
+        for json in ["null", r#""NULL_VALUE""#, "0"] {
+            assert_eq!(
+                serde_json::from_str::<NullValue>(json).unwrap(),
+                NullValue,
+                "parsed {}",
+                json
+            );
+        }
+        for json in ["1", r#""NULL""#, "{}"] {
+            assert!(
+                serde_json::from_str::<NullValue>(json).is_err(),
+                "parsed {}",
+                json
+            );
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+/// The `kind` oneof of `google.type.PhoneNumber`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PhoneNumberKind {
+    /// A number in E.164 format, e.g. `+41446681800`.
+    E164Number(String),
+    /// A short code, which is only reachable within its region.
+    ShortCode(ShortCode),
+}
+
+/// A `google.type.PhoneNumber.ShortCode`, e.g. the emergency number `911` in the `US`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShortCode {
+    /// CLDR region code of the region in which the short code is valid, e.g. `US`.
+    #[serde(default)]
+    pub region_code: String,
+    /// The short code digits, without a leading plus sign or country calling code.
+    #[serde(default)]
+    pub number: String,
+}
+
+/// A `google.type.PhoneNumber`, as either an E.164 number or a short code, with an optional
+/// extension.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "PhoneNumberRepr", into = "PhoneNumberRepr")]
+pub struct PhoneNumber {
+    pub kind: Option<PhoneNumberKind>,
+    /// The extension, which is dialed after connecting to the number, e.g. `123`.
+    pub extension: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum PhoneNumberError {
+    /// Both `e164Number` and `shortCode` were set, but they are mutually exclusive.
+    ConflictingKind,
+}
+
+impl Display for PhoneNumberError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PhoneNumberError::ConflictingKind => {
+                f.write_str("only one of 'e164Number' and 'shortCode' may be set")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PhoneNumberError {}
+
+/// The wire representation of a [`PhoneNumber`], with the oneof members as separate fields.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PhoneNumberRepr {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    e164_number: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    short_code: Option<ShortCode>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    extension: Option<String>,
+}
+
+impl TryFrom<PhoneNumberRepr> for PhoneNumber {
+    type Error = PhoneNumberError;
+
+    fn try_from(repr: PhoneNumberRepr) -> Result<Self, Self::Error> {
+        let kind = match (repr.e164_number, repr.short_code) {
+            (Some(_), Some(_)) => return Err(PhoneNumberError::ConflictingKind),
+            (Some(number), None) => Some(PhoneNumberKind::E164Number(number)),
+            (None, Some(short_code)) => Some(PhoneNumberKind::ShortCode(short_code)),
+            (None, None) => None,
+        };
+        Ok(PhoneNumber {
+            kind,
+            extension: repr.extension,
+        })
+    }
+}
+
+impl From<PhoneNumber> for PhoneNumberRepr {
+    fn from(phone_number: PhoneNumber) -> Self {
+        let (e164_number, short_code) = match phone_number.kind {
+            Some(PhoneNumberKind::E164Number(number)) => (Some(number), None),
+            Some(PhoneNumberKind::ShortCode(short_code)) => (None, Some(short_code)),
+            None => (None, None),
+        };
+        PhoneNumberRepr {
+            e164_number,
+            short_code,
+            extension: phone_number.extension,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{PhoneNumber, PhoneNumberKind, ShortCode};
+
+    #[test]
+    fn e164_number_roundtrip() {
+        let phone_number = PhoneNumber {
+            kind: Some(PhoneNumberKind::E164Number("+41446681800".to_string())),
+            extension: Some("123".to_string()),
+        };
+        let json = serde_json::to_string(&phone_number).unwrap();
+        assert_eq!(json, r#"{"e164Number":"+41446681800","extension":"123"}"#);
+        assert_eq!(phone_number, serde_json::from_str(&json).unwrap());
+    }
+
+    #[test]
+    fn short_code_roundtrip() {
+        let phone_number = PhoneNumber {
+            kind: Some(PhoneNumberKind::ShortCode(ShortCode {
+                region_code: "US".to_string(),
+                number: "911".to_string(),
+            })),
+            extension: None,
+        };
+        let json = serde_json::to_string(&phone_number).unwrap();
+        assert_eq!(json, r#"{"shortCode":{"regionCode":"US","number":"911"}}"#);
+        assert_eq!(phone_number, serde_json::from_str(&json).unwrap());
+    }
+
+    #[test]
+    fn conflicting_kind() {
+        let err = serde_json::from_str::<PhoneNumber>(
+            r#"{"e164Number": "+1", "shortCode": {"regionCode": "US", "number": "911"}}"#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("only one of"), "{}", err);
+        assert_eq!(
+            PhoneNumber::default(),
+            serde_json::from_str::<PhoneNumber>("{}").unwrap()
+        );
+    }
+}
@@ -0,0 +1,124 @@
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+use crate::serde::int32_value;
+
+/// A `google.type.PostalAddress`, representing a postal address for postal delivery or payments.
+///
+/// Empty `address_lines` and `recipients` are omitted on serialization, and default to empty
+/// when missing. `revision` is an int32, which is a JSON number, but also accepted as a string.
+#[serde_as]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PostalAddress {
+    /// The schema revision, where 0 is the latest revision.
+    #[serde_as(as = "int32_value::Wrapper")]
+    #[serde(default)]
+    pub revision: i32,
+    /// CLDR region code of the country or region of the address, e.g. `CH`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region_code: Option<String>,
+    /// BCP-47 language code of the contents of this address, e.g. `zh-Hant`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language_code: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub postal_code: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sorting_code: Option<String>,
+    /// The highest administrative subdivision, e.g. a state, province or prefecture.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub administrative_area: Option<String>,
+    /// The city or town of the address.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locality: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sublocality: Option<String>,
+    /// Unstructured address lines describing the lower levels of an address.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub address_lines: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub recipients: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub organization: Option<String>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::PostalAddress;
+    use crate::serde::str_like;
+    use serde::{Deserialize, Serialize};
+    use serde_with::serde_as;
+
+    #[test]
+    fn postal_address_roundtrip() {
+        let address = PostalAddress {
+            region_code: Some("US".to_string()),
+            postal_code: Some("94043".to_string()),
+            administrative_area: Some("CA".to_string()),
+            locality: Some("Mountain View".to_string()),
+            address_lines: vec![
+                "1600 Amphitheatre Parkway".to_string(),
+                "Building 40".to_string(),
+            ],
+            recipients: vec!["Jane Doe".to_string()],
+            ..Default::default()
+        };
+        let json = serde_json::to_value(&address).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "revision": 0,
+                "regionCode": "US",
+                "postalCode": "94043",
+                "administrativeArea": "CA",
+                "locality": "Mountain View",
+                "addressLines": ["1600 Amphitheatre Parkway", "Building 40"],
+                "recipients": ["Jane Doe"],
+            })
+        );
+        assert_eq!(address, serde_json::from_value(json).unwrap());
+    }
+
+    #[test]
+    fn numeric_fields() {
+        #[serde_as]
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Location {
+            // an int64, which proto3 encodes as a string
+            #[serde_as(as = "str_like::Wrapper")]
+            location_id: i64,
+            address: PostalAddress,
+        }
+
+        let location = Location {
+            location_id: 9_007_199_254_740_993,
+            address: PostalAddress {
+                revision: 1,
+                ..Default::default()
+            },
+        };
+        let json = serde_json::to_string(&location).unwrap();
+        assert_eq!(
+            json,
+            r#"{"locationId":"9007199254740993","address":{"revision":1}}"#
+        );
+        assert_eq!(location, serde_json::from_str(&json).unwrap());
+        assert_eq!(
+            location,
+            serde_json::from_str(r#"{"locationId":"9007199254740993","address":{"revision":"1"}}"#)
+                .unwrap()
+        );
+        assert!(serde_json::from_str::<PostalAddress>(r#"{"revision":"4294967296"}"#).is_err());
+    }
+
+    #[test]
+    fn empty_postal_address() {
+        let address = PostalAddress::default();
+        assert_eq!(
+            serde_json::to_string(&address).unwrap(),
+            r#"{"revision":0}"#
+        );
+        assert_eq!(address, serde_json::from_str("{}").unwrap());
+    }
+}
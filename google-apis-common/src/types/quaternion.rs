@@ -0,0 +1,94 @@
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+use crate::serde::double_value;
+
+/// A `google.type.Quaternion`, commonly used to represent rotations in 3D space.
+///
+/// Omitted components default to 0.
+#[serde_as]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Quaternion {
+    #[serde_as(as = "double_value::Wrapper")]
+    #[serde(default)]
+    pub x: f64,
+    #[serde_as(as = "double_value::Wrapper")]
+    #[serde(default)]
+    pub y: f64,
+    #[serde_as(as = "double_value::Wrapper")]
+    #[serde(default)]
+    pub z: f64,
+    #[serde_as(as = "double_value::Wrapper")]
+    #[serde(default)]
+    pub w: f64,
+}
+
+impl Quaternion {
+    /// Returns the Euclidean norm of the quaternion.
+    pub fn norm(&self) -> f64 {
+        (self.x * self.x + self.y * self.y + self.z * self.z + self.w * self.w).sqrt()
+    }
+
+    /// Scales the quaternion to unit length, as is expected for rotations.
+    ///
+    /// A zero quaternion has no direction and is left unchanged.
+    pub fn normalize(&mut self) {
+        let norm = self.norm();
+        if norm != 0.0 {
+            self.x /= norm;
+            self.y /= norm;
+            self.z /= norm;
+            self.w /= norm;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Quaternion;
+
+    #[test]
+    fn quaternion_roundtrip() {
+        let quaternion = Quaternion {
+            x: 0.5,
+            y: -0.5,
+            z: 0.0,
+            w: 1.0,
+        };
+        let json = serde_json::to_string(&quaternion).unwrap();
+        assert_eq!(json, r#"{"x":0.5,"y":-0.5,"z":0.0,"w":1.0}"#);
+        assert_eq!(quaternion, serde_json::from_str(&json).unwrap());
+        assert_eq!(
+            Quaternion {
+                w: 1.0,
+                ..Default::default()
+            },
+            serde_json::from_str(r#"{"w": 1}"#).unwrap()
+        );
+    }
+
+    #[test]
+    fn normalize() {
+        let mut quaternion = Quaternion {
+            x: 1.0,
+            y: 1.0,
+            z: 1.0,
+            w: 1.0,
+        };
+        quaternion.normalize();
+        assert_eq!(
+            quaternion,
+            Quaternion {
+                x: 0.5,
+                y: 0.5,
+                z: 0.5,
+                w: 0.5
+            }
+        );
+        assert_eq!(quaternion.norm(), 1.0);
+
+        let mut zero = Quaternion::default();
+        zero.normalize();
+        assert_eq!(zero, Quaternion::default());
+    }
+}
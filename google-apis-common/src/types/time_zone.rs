@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+/// A `google.type.TimeZone`, an IANA time zone with an optional database version.
+///
+/// Only the message is modelled. Resolving `id` to the rules of the zone is left to a time zone
+/// database like the `chrono-tz` crate, e.g. with `time_zone.id.parse::<chrono_tz::Tz>()`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimeZone {
+    /// IANA Time Zone Database time zone, e.g. `America/New_York`.
+    pub id: String,
+    /// IANA Time Zone Database version number, e.g. `2019a`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::TimeZone;
+
+    #[test]
+    fn time_zone_roundtrip() {
+        let time_zone = TimeZone {
+            id: "America/New_York".to_string(),
+            version: Some("2019a".to_string()),
+        };
+        let json = serde_json::to_string(&time_zone).unwrap();
+        assert_eq!(json, r#"{"id":"America/New_York","version":"2019a"}"#);
+        assert_eq!(time_zone, serde_json::from_str(&json).unwrap());
+
+        let time_zone: TimeZone = serde_json::from_str(r#"{"id":"Europe/Zurich"}"#).unwrap();
+        assert_eq!(time_zone.version, None);
+        assert_eq!(
+            serde_json::to_string(&time_zone).unwrap(),
+            r#"{"id":"Europe/Zurich"}"#
+        );
+        assert!(serde_json::from_str::<TimeZone>(r#"{"version":"2019a"}"#).is_err());
+    }
+}
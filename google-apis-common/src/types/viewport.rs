@@ -0,0 +1,105 @@
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::lat_lng::LatLng;
+
+/// A `google.type.Viewport`, a latitude-longitude rectangle given by its low and high corners.
+///
+/// Deserialization fails if the latitude of `low` is above the one of `high`. The longitude of
+/// `low` may be above the one of `high`, in which case the viewport crosses the antimeridian.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "ViewportRepr")]
+pub struct Viewport {
+    pub low: LatLng,
+    pub high: LatLng,
+}
+
+impl Viewport {
+    /// Returns true if the viewport crosses the 180 degree meridian.
+    pub fn crosses_antimeridian(&self) -> bool {
+        self.low.longitude > self.high.longitude
+    }
+}
+
+#[derive(Debug)]
+pub enum ViewportError {
+    /// The latitude of `low` was above the latitude of `high`.
+    InvertedLatitude,
+}
+
+impl Display for ViewportError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ViewportError::InvertedLatitude => {
+                f.write_str("the low latitude must not be above the high latitude")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ViewportError {}
+
+#[derive(Deserialize)]
+struct ViewportRepr {
+    #[serde(default)]
+    low: LatLng,
+    #[serde(default)]
+    high: LatLng,
+}
+
+impl TryFrom<ViewportRepr> for Viewport {
+    type Error = ViewportError;
+
+    fn try_from(repr: ViewportRepr) -> Result<Self, Self::Error> {
+        if repr.low.latitude > repr.high.latitude {
+            return Err(ViewportError::InvertedLatitude);
+        }
+        Ok(Viewport {
+            low: repr.low,
+            high: repr.high,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Viewport;
+    use crate::types::lat_lng::LatLng;
+
+    #[test]
+    fn viewport_roundtrip() {
+        let viewport = Viewport {
+            low: LatLng {
+                latitude: 40.5,
+                longitude: -74.25,
+            },
+            high: LatLng {
+                latitude: 40.75,
+                longitude: -73.5,
+            },
+        };
+        let json = serde_json::to_string(&viewport).unwrap();
+        assert_eq!(
+            json,
+            r#"{"low":{"latitude":40.5,"longitude":-74.25},"high":{"latitude":40.75,"longitude":-73.5}}"#
+        );
+        assert_eq!(viewport, serde_json::from_str(&json).unwrap());
+        assert!(!viewport.crosses_antimeridian());
+    }
+
+    #[test]
+    fn viewport_crossing_antimeridian() {
+        let viewport: Viewport = serde_json::from_str(
+            r#"{"low": {"latitude": -20, "longitude": 170}, "high": {"latitude": -10, "longitude": -170}}"#,
+        )
+        .unwrap();
+        assert!(viewport.crosses_antimeridian());
+
+        let err = serde_json::from_str::<Viewport>(
+            r#"{"low": {"latitude": 10, "longitude": 0}, "high": {"latitude": -10, "longitude": 1}}"#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("low latitude"), "{}", err);
+    }
+}
@@ -0,0 +1,268 @@
+//! Derive macros for types which are (de)serialized using the protobuf JSON mapping.
+//!
+//! `#[derive(GoogleSerde)]` implements `Serialize` and `Deserialize` for a struct with named
+//! fields, picking the matching helper of `google_apis_common::serde` for each field based on its
+//! type:
+//!
+//! * `chrono::Duration` uses `google_apis_common::serde::duration::Wrapper`. The type has to be
+//!   spelled with its `chrono::` prefix, e.g. `chrono::Duration` or
+//!   `google_apis_common::chrono::Duration`, as types are matched by name, and a bare `Duration`
+//!   may as well be `std::time::Duration`
+//! * `Vec<u8>` uses `google_apis_common::serde::urlsafe_base64::Wrapper`
+//! * `i64` and `u64` use `serde_with::DisplayFromStr`
+//!
+//! These are also applied within `Option<_>` and `Vec<_>`. All other fields, as well as
+//! `#[serde(...)]` attributes on the struct and its fields, are passed through to serde's own
+//! derive. The deriving crate needs to depend on `serde` and `google-apis-common`.
+//!
+//! `#[derive(FieldNames)]` implements `google_apis_common::FieldNames`, which lists the names of
+//! the fields of a struct to build a `FieldMask` requesting all of them.
+use proc_macro2::{TokenStream, TokenTree};
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::{
+    parse_macro_input, Attribute, Data, DeriveInput, Error, Fields, GenericArgument, PathArguments,
+    Type,
+};
+
+#[proc_macro_derive(GoogleSerde, attributes(serde))]
+pub fn derive_google_serde(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Implements `google_apis_common::FieldNames` for a struct with named fields, returning the
+/// names of its fields as declared, without any `r#` prefix.
+#[proc_macro_derive(FieldNames)]
+pub fn derive_field_names(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_field_names(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand_field_names(input: DeriveInput) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new_spanned(
+                    &input,
+                    "FieldNames requires a struct with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                &input,
+                "FieldNames can only be derived for structs",
+            ))
+        }
+    };
+    let names = fields.iter().map(|field| {
+        field
+            .ident
+            .as_ref()
+            .expect("named field")
+            .unraw()
+            .to_string()
+    });
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::google_apis_common::FieldNames for #name #ty_generics #where_clause {
+            fn field_names() -> &'static [&'static str] {
+                &[#(#names),*]
+            }
+        }
+    })
+}
+
+/// Returns the last path segment of `ty` along with its generic arguments, if `ty` is a path.
+fn last_segment(ty: &Type) -> Option<(String, Vec<&Type>)> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    let args = match &segment.arguments {
+        PathArguments::AngleBracketed(args) => args
+            .args
+            .iter()
+            .filter_map(|arg| match arg {
+                GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    Some((segment.ident.to_string(), args))
+}
+
+/// Returns true if `ty` is spelled as a path ending in `chrono::Duration`.
+fn is_chrono_duration(ty: &Type) -> bool {
+    let Type::Path(path) = ty else {
+        return false;
+    };
+    let segments: Vec<_> = path.path.segments.iter().rev().take(2).collect();
+    matches!(
+        segments.as_slice(),
+        [duration, chrono] if duration.ident == "Duration"
+            && duration.arguments.is_empty()
+            && chrono.ident == "chrono"
+    )
+}
+
+/// Returns the `serde_with` adapter type to use for a field of type `ty`, or `None` if the field
+/// is handled by serde directly.
+fn adapter_for(ty: &Type) -> Option<TokenStream> {
+    if is_chrono_duration(ty) {
+        return Some(quote!(::google_apis_common::serde::duration::Wrapper));
+    }
+    let (name, args) = last_segment(ty)?;
+    match (name.as_str(), args.as_slice()) {
+        ("i64" | "u64", []) => Some(quote!(::google_apis_common::serde_with::DisplayFromStr)),
+        ("Vec", [inner]) if last_segment(inner).is_some_and(|(name, _)| name == "u8") => {
+            Some(quote!(::google_apis_common::serde::urlsafe_base64::Wrapper))
+        }
+        ("Option", [inner]) => adapter_for(inner).map(|adapter| quote!(Option<#adapter>)),
+        ("Vec", [inner]) => adapter_for(inner).map(|adapter| quote!(Vec<#adapter>)),
+        _ => None,
+    }
+}
+
+fn is_option(ty: &Type) -> bool {
+    last_segment(ty).is_some_and(|(name, args)| name == "Option" && args.len() == 1)
+}
+
+fn serde_attrs(attrs: &[Attribute]) -> impl Iterator<Item = &Attribute> {
+    attrs.iter().filter(|attr| attr.path().is_ident("serde"))
+}
+
+/// Returns true if one of the `#[serde(...)]` attributes contains `default`, either bare or as
+/// `default = "..."`.
+fn has_serde_default(attrs: &[&Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        let syn::Meta::List(list) = &attr.meta else {
+            return false;
+        };
+        // only top-level idents at the start of an item, as values are literals
+        let mut item_start = true;
+        list.tokens.clone().into_iter().any(|token| {
+            let is_default =
+                item_start && matches!(&token, TokenTree::Ident(ident) if ident == "default");
+            item_start = matches!(&token, TokenTree::Punct(punct) if punct.as_char() == ',');
+            is_default
+        })
+    })
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "GoogleSerde does not support generic types",
+        ));
+    }
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new_spanned(
+                    &input,
+                    "GoogleSerde requires a struct with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                &input,
+                "GoogleSerde can only be derived for structs",
+            ))
+        }
+    };
+    let container_attrs: Vec<_> = serde_attrs(&input.attrs).collect();
+
+    let mut ser_fields = Vec::new();
+    let mut de_fields = Vec::new();
+    let mut idents = Vec::new();
+    for field in fields {
+        let ident = field.ident.as_ref().expect("named field");
+        let ty = &field.ty;
+        let attrs: Vec<_> = serde_attrs(&field.attrs).collect();
+        match adapter_for(ty) {
+            Some(adapter) => {
+                let ser_with = format!(
+                    "::google_apis_common::serde_with::As::<&'a {}>::serialize",
+                    adapter
+                );
+                let de_with = format!(
+                    "::google_apis_common::serde_with::As::<{}>::deserialize",
+                    adapter
+                );
+                // `deserialize_with` loses serde's implicit `None` for missing `Option` fields
+                let default = (is_option(ty) && !has_serde_default(&attrs))
+                    .then(|| quote!(#[serde(default)]));
+                ser_fields.push(quote! {
+                    #(#attrs)*
+                    #[serde(serialize_with = #ser_with)]
+                    #ident: &'a #ty
+                });
+                de_fields.push(quote! {
+                    #(#attrs)*
+                    #default
+                    #[serde(deserialize_with = #de_with)]
+                    #ident: #ty
+                });
+            }
+            None => {
+                ser_fields.push(quote!(#(#attrs)* #ident: &'a #ty));
+                de_fields.push(quote!(#(#attrs)* #ident: #ty));
+            }
+        }
+        idents.push(ident);
+    }
+
+    Ok(quote! {
+        const _: () = {
+            #[derive(::serde::Serialize)]
+            #(#container_attrs)*
+            struct GoogleSerdeSer<'a> {
+                #(#ser_fields,)*
+            }
+
+            #[derive(::serde::Deserialize)]
+            #(#container_attrs)*
+            struct GoogleSerdeDe {
+                #(#de_fields,)*
+            }
+
+            impl ::serde::Serialize for #name {
+                fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+                where
+                    S: ::serde::Serializer,
+                {
+                    ::serde::Serialize::serialize(
+                        &GoogleSerdeSer {
+                            #(#idents: &self.#idents,)*
+                        },
+                        serializer,
+                    )
+                }
+            }
+
+            impl<'de> ::serde::Deserialize<'de> for #name {
+                fn deserialize<D>(deserializer: D) -> ::std::result::Result<Self, D::Error>
+                where
+                    D: ::serde::Deserializer<'de>,
+                {
+                    let GoogleSerdeDe { #(#idents,)* } =
+                        ::serde::Deserialize::deserialize(deserializer)?;
+                    Ok(#name { #(#idents,)* })
+                }
+            }
+        };
+    })
+}
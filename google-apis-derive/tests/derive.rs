@@ -0,0 +1,113 @@
+use google_apis_common::chrono;
+use google_apis_derive::GoogleSerde;
+
+#[derive(GoogleSerde, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct Operation {
+    retry_delay: Option<chrono::Duration>,
+    payload: Vec<u8>,
+    total_bytes: i64,
+    sizes: Vec<u64>,
+    name: String,
+    #[serde(rename = "done")]
+    finished: Option<bool>,
+}
+
+fn operation() -> Operation {
+    Operation {
+        retry_delay: Some(chrono::Duration::milliseconds(1500)),
+        payload: b"hello world".to_vec(),
+        total_bytes: i64::MAX,
+        sizes: vec![1, 2],
+        name: "operations/1".to_string(),
+        finished: Some(true),
+    }
+}
+
+#[test]
+fn serialize_uses_field_helpers() {
+    assert_eq!(
+        serde_json::to_value(operation()).unwrap(),
+        serde_json::json!({
+            "retryDelay": "1.500s",
+            "payload": "aGVsbG8gd29ybGQ=",
+            "totalBytes": "9223372036854775807",
+            "sizes": ["1", "2"],
+            "name": "operations/1",
+            "done": true,
+        })
+    );
+}
+
+#[test]
+fn roundtrip() {
+    let json = serde_json::to_string(&operation()).unwrap();
+    assert_eq!(operation(), serde_json::from_str(&json).unwrap());
+}
+
+#[test]
+fn missing_options_are_none() {
+    let parsed: Operation =
+        serde_json::from_str(r#"{"payload": "", "totalBytes": "0", "sizes": [], "name": ""}"#)
+            .unwrap();
+    assert_eq!(parsed.retry_delay, None);
+    assert_eq!(parsed.finished, None);
+}
+
+#[derive(GoogleSerde, Debug, PartialEq)]
+struct Timeouts {
+    #[serde(default)]
+    connect: Option<chrono::Duration>,
+    #[serde(default = "default_read")]
+    read: Option<chrono::Duration>,
+    // not a chrono duration, so left to serde
+    local: Option<std::time::Duration>,
+}
+
+fn default_read() -> Option<chrono::Duration> {
+    Some(chrono::Duration::seconds(30))
+}
+
+#[test]
+fn user_defaults_are_kept() {
+    let parsed: Timeouts = serde_json::from_str(r#"{"local": null}"#).unwrap();
+    assert_eq!(
+        parsed,
+        Timeouts {
+            connect: None,
+            read: Some(chrono::Duration::seconds(30)),
+            local: None,
+        }
+    );
+    let timeouts = Timeouts {
+        connect: Some(chrono::Duration::seconds(5)),
+        read: None,
+        local: Some(std::time::Duration::from_secs(1)),
+    };
+    assert_eq!(
+        serde_json::to_value(&timeouts).unwrap(),
+        serde_json::json!({
+            "connect": "5s",
+            "read": null,
+            "local": {"secs": 1, "nanos": 0},
+        })
+    );
+}
+
+#[derive(google_apis_derive::FieldNames)]
+#[allow(dead_code)]
+struct User {
+    display_name: String,
+    r#type: String,
+}
+
+#[test]
+fn field_names() {
+    use google_apis_common::{FieldMask, FieldNames};
+
+    assert_eq!(User::field_names(), &["display_name", "type"]);
+    assert_eq!(
+        FieldMask::from_struct_fields::<User>().to_string(),
+        "displayName,type"
+    );
+}